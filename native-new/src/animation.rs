@@ -0,0 +1,387 @@
+//! Native property animations
+//!
+//! JS only starts the animation, everything else (timing, easing, interpolation)
+//! happens here so there's no need to send anything over FFI for every frame.
+//!
+//! Animator itself has no idea about the scene nor renderer, it just computes the
+//! values, it's up to the window to apply them (ideally without rebuilding the
+//! display list)
+
+use crate::api::{Color, Matrix, SurfaceId, Transform, TransformOp, WindowEvent};
+use crate::generated::{AnimatedProperty, AnimatedValue, Easing, Vector2f};
+use std::collections::BTreeSet;
+use std::mem::discriminant;
+use std::time::Instant;
+
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub surface: SurfaceId,
+    pub property: AnimatedProperty,
+    pub from: AnimatedValue,
+    pub to: AnimatedValue,
+    // ms
+    pub duration: f32,
+    pub easing: Easing,
+    // ms
+    pub delay: f32,
+    // 0 means infinite
+    pub iterations: u32,
+}
+
+pub struct Animator {
+    running: Vec<(Instant, Animation)>,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Animator { running: vec![] }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.running.is_empty()
+    }
+
+    pub fn add(&mut self, animation: Animation) {
+        // there can be only one animation of the given property
+        self.running.retain(|(_, a)| {
+            a.surface != animation.surface || discriminant(&a.property) != discriminant(&animation.property)
+        });
+
+        self.running.push((Instant::now(), animation));
+    }
+
+    /// Surfaces which have the given property animated (including delayed animations)
+    pub fn animated_surfaces(&self, property: AnimatedProperty) -> BTreeSet<SurfaceId> {
+        self.running
+            .iter()
+            .filter(|(_, a)| discriminant(&a.property) == discriminant(&property))
+            .map(|(_, a)| a.surface)
+            .collect()
+    }
+
+    /// Returns current values of all the (already started) animations
+    /// and also the animations which have just finished (and were removed)
+    pub fn tick(&mut self, now: Instant) -> (Vec<(SurfaceId, AnimatedValue)>, Vec<Animation>) {
+        let mut values = vec![];
+        let mut finished = vec![];
+
+        for (start, animation) in std::mem::replace(&mut self.running, vec![]) {
+            let elapsed = now.duration_since(start);
+            let elapsed = (elapsed.as_secs() as f32) * 1000. + (elapsed.subsec_micros() as f32) / 1000.;
+
+            match animation.progress(elapsed) {
+                None => self.running.push((start, animation)),
+                Some((t, done)) => {
+                    // should be validated already but it's not a reason to stop the others
+                    let value = match interpolate(&animation.from, &animation.to, ease(&animation.easing, t)) {
+                        Some(value) => value,
+                        None => {
+                            error!("invalid animation {:?}", animation);
+                            continue;
+                        }
+                    };
+                    values.push((animation.surface, value));
+
+                    if done {
+                        finished.push(animation);
+                    } else {
+                        self.running.push((start, animation));
+                    }
+                }
+            }
+        }
+
+        (values, finished)
+    }
+}
+
+impl Animation {
+    /// Both values have to be of the animated property (it comes from js so it can be anything)
+    pub fn is_valid(&self) -> bool {
+        let kind = |value: &AnimatedValue| match value {
+            AnimatedValue::Opacity(_) => AnimatedProperty::Opacity,
+            AnimatedValue::Transform(_) => AnimatedProperty::Transform,
+            AnimatedValue::BackgroundColor(_) => AnimatedProperty::BackgroundColor,
+            AnimatedValue::ScrollOffset(_) => AnimatedProperty::ScrollOffset,
+        };
+
+        discriminant(&kind(&self.from)) == discriminant(&self.property)
+            && discriminant(&kind(&self.to)) == discriminant(&self.property)
+    }
+
+    pub fn end_event(&self) -> WindowEvent {
+        WindowEvent::AnimationEnd { target: self.surface, property: self.property.clone() }
+    }
+
+    // (t, finished) or None if it has not started yet
+    fn progress(&self, elapsed: f32) -> Option<(f32, bool)> {
+        let elapsed = elapsed - self.delay;
+
+        if elapsed < 0. {
+            return None;
+        }
+
+        if self.duration <= 0. {
+            return Some((1., true));
+        }
+
+        let iteration = elapsed / self.duration;
+
+        if (self.iterations != 0) && (iteration >= self.iterations as f32) {
+            return Some((1., true));
+        }
+
+        Some((iteration.fract(), false))
+    }
+}
+
+pub fn ease(easing: &Easing, t: f32) -> f32 {
+    match easing {
+        Easing::Linear => t,
+        Easing::EaseIn => cubic_bezier((0.42, 0.), (1., 1.), t),
+        Easing::EaseOut => cubic_bezier((0., 0.), (0.58, 1.), t),
+        Easing::EaseInOut => cubic_bezier((0.42, 0.), (0.58, 1.), t),
+    }
+}
+
+// like in CSS, find `s` for which x(s) == t (newton) and then return y(s)
+fn cubic_bezier((x1, y1): (f32, f32), (x2, y2): (f32, f32), t: f32) -> f32 {
+    let bezier = |a1: f32, a2: f32, s: f32| {
+        3. * a1 * s * (1. - s).powi(2) + 3. * a2 * s.powi(2) * (1. - s) + s.powi(3)
+    };
+    let derivative = |a1: f32, a2: f32, s: f32| {
+        3. * a1 * (1. - s).powi(2) + 6. * (a2 - a1) * s * (1. - s) + 3. * (1. - a2) * s.powi(2)
+    };
+
+    let mut s = t;
+
+    for _ in 0..8 {
+        let d = derivative(x1, x2, s);
+
+        if d.abs() < 1e-6 {
+            break;
+        }
+
+        s = (s - (bezier(x1, x2, s) - t) / d).max(0.).min(1.);
+    }
+
+    bezier(y1, y2, s)
+}
+
+// None if the values are not of the same kind
+pub fn interpolate(from: &AnimatedValue, to: &AnimatedValue, t: f32) -> Option<AnimatedValue> {
+    Some(match (from, to) {
+        (AnimatedValue::Opacity(a), AnimatedValue::Opacity(b)) => AnimatedValue::Opacity(lerp(*a, *b, t)),
+        (AnimatedValue::BackgroundColor(a), AnimatedValue::BackgroundColor(b)) => {
+            AnimatedValue::BackgroundColor(lerp_color(a, b, t))
+        }
+        (AnimatedValue::ScrollOffset(a), AnimatedValue::ScrollOffset(b)) => {
+            AnimatedValue::ScrollOffset(lerp_vector(a, b, t))
+        }
+        (AnimatedValue::Transform(a), AnimatedValue::Transform(b)) => AnimatedValue::Transform(lerp_transform(a, b, t)),
+        _ => return None,
+    })
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn lerp_color(a: &Color, b: &Color, t: f32) -> Color {
+    let c = |a: u8, b: u8| lerp(a as f32, b as f32, t).round() as u8;
+
+    Color(c(a.0, b.0), c(a.1, b.1), c(a.2, b.2), c(a.3, b.3))
+}
+
+fn lerp_vector(a: &Vector2f, b: &Vector2f, t: f32) -> Vector2f {
+    Vector2f(lerp(a.0, b.0, t), lerp(a.1, b.1, t))
+}
+
+// ops are interpolated one by one if they are of the same kind,
+// otherwise there's no meaningful way to do it and we just switch in the middle
+fn lerp_transform(a: &Transform, b: &Transform, t: f32) -> Transform {
+    let compatible = a.ops.len() == b.ops.len()
+        && a.ops.iter().zip(b.ops.iter()).all(|(a, b)| discriminant(a) == discriminant(b));
+
+    if !compatible {
+        return if t < 0.5 { a.clone() } else { b.clone() };
+    }
+
+    let ops = a.ops
+        .iter()
+        .zip(b.ops.iter())
+        .map(|ops| match ops {
+            (TransformOp::Matrix(a), TransformOp::Matrix(b)) => TransformOp::Matrix(Matrix(
+                lerp(a.0, b.0, t),
+                lerp(a.1, b.1, t),
+                lerp(a.2, b.2, t),
+                lerp(a.3, b.3, t),
+                lerp(a.4, b.4, t),
+                lerp(a.5, b.5, t),
+            )),
+            (TransformOp::Translate(a), TransformOp::Translate(b)) => TransformOp::Translate(lerp_vector(a, b, t)),
+            (TransformOp::Scale(a), TransformOp::Scale(b)) => TransformOp::Scale(lerp_vector(a, b, t)),
+            (TransformOp::Rotate(a), TransformOp::Rotate(b)) => TransformOp::Rotate(lerp(*a, *b, t)),
+            (TransformOp::Skew(a), TransformOp::Skew(b)) => TransformOp::Skew(lerp_vector(a, b, t)),
+            _ => unreachable!(),
+        })
+        .collect();
+
+    Transform { ops, origin: b.origin.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_animation(iterations: u32) -> Animation {
+        Animation {
+            surface: 1,
+            property: AnimatedProperty::Opacity,
+            from: AnimatedValue::Opacity(0.),
+            to: AnimatedValue::Opacity(1.),
+            duration: 100.,
+            easing: Easing::Linear,
+            delay: 50.,
+            iterations,
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let animation = test_animation(2);
+
+        assert_eq!(animation.progress(0.), None);
+        assert_eq!(animation.progress(100.), Some((0.5, false)));
+        assert_eq!(animation.progress(200.), Some((0.5, false)));
+        assert_eq!(animation.progress(250.), Some((1., true)));
+
+        let animation = test_animation(0);
+
+        assert_eq!(animation.progress(10050.), Some((0., false)));
+    }
+
+    #[test]
+    fn test_ease() {
+        for easing in &[Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert!(ease(easing, 0.).abs() < 0.001);
+            assert!((ease(easing, 1.) - 1.).abs() < 0.001);
+        }
+
+        assert!((ease(&Easing::EaseInOut, 0.5) - 0.5).abs() < 0.001);
+        assert!(ease(&Easing::EaseIn, 0.25) < 0.25);
+        assert!(ease(&Easing::EaseOut, 0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        match interpolate(&AnimatedValue::Opacity(0.), &AnimatedValue::Opacity(0.5), 0.5) {
+            Some(AnimatedValue::Opacity(v)) => assert_eq!(v, 0.25),
+            _ => panic!(),
+        }
+
+        match interpolate(
+            &AnimatedValue::BackgroundColor(Color(0, 0, 0, 255)),
+            &AnimatedValue::BackgroundColor(Color(255, 100, 0, 255)),
+            0.5,
+        ) {
+            Some(AnimatedValue::BackgroundColor(Color(r, g, b, a))) => assert_eq!((r, g, b, a), (128, 50, 0, 255)),
+            _ => panic!(),
+        }
+
+        assert!(interpolate(&AnimatedValue::Opacity(0.), &AnimatedValue::ScrollOffset(Vector2f(0., 0.)), 0.5).is_none());
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(test_animation(1).is_valid());
+
+        let mut animation = test_animation(1);
+        animation.to = AnimatedValue::ScrollOffset(Vector2f(0., 0.));
+        assert!(!animation.is_valid());
+
+        let mut animation = test_animation(1);
+        animation.property = AnimatedProperty::ScrollOffset;
+        assert!(!animation.is_valid());
+    }
+
+    fn opacities(values: &[(SurfaceId, AnimatedValue)]) -> Vec<(SurfaceId, f32)> {
+        values
+            .iter()
+            .map(|(surface, value)| match value {
+                AnimatedValue::Opacity(v) => (*surface, *v),
+                _ => panic!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tick() {
+        let mut animator = Animator::new();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        animator.add(test_animation(2));
+
+        // delay
+        let (values, finished) = animator.tick(at(10));
+        assert!(values.is_empty() && finished.is_empty());
+        assert!(animator.is_running());
+
+        let (values, finished) = animator.tick(at(100));
+        let (surface, opacity) = opacities(&values)[0];
+        assert_eq!(surface, 1);
+        assert!((opacity - 0.5).abs() < 0.05);
+        assert!(finished.is_empty());
+
+        // second iteration
+        let (values, _) = animator.tick(at(225));
+        assert!((opacities(&values)[0].1 - 0.75).abs() < 0.05);
+        assert!(animator.is_running());
+
+        // completion (with the final value)
+        let (values, finished) = animator.tick(at(300));
+        assert_eq!(opacities(&values), vec![(1, 1.)]);
+        assert_eq!(finished.len(), 1);
+        assert!(!animator.is_running());
+
+        match finished[0].end_event() {
+            WindowEvent::AnimationEnd { target: 1, property: AnimatedProperty::Opacity } => {}
+            e => panic!("unexpected {:?}", e),
+        }
+
+        // nothing else
+        let (values, finished) = animator.tick(at(400));
+        assert!(values.is_empty() && finished.is_empty());
+    }
+
+    #[test]
+    fn test_tick_infinite() {
+        let mut animator = Animator::new();
+
+        animator.add(test_animation(0));
+        let (values, finished) = animator.tick(Instant::now() + Duration::from_secs(10));
+
+        assert_eq!(values.len(), 1);
+        assert!(finished.is_empty());
+        assert!(animator.is_running());
+    }
+
+    #[test]
+    fn test_tick_invalid() {
+        let mut animator = Animator::new();
+        let mut invalid = test_animation(1);
+        invalid.surface = 2;
+        invalid.to = AnimatedValue::ScrollOffset(Vector2f(0., 0.));
+
+        animator.add(invalid);
+        animator.add(test_animation(1));
+
+        // the invalid one is dropped but the other one keeps running
+        let (values, _) = animator.tick(Instant::now() + Duration::from_millis(100));
+        assert_eq!(opacities(&values).len(), 1);
+        assert_eq!(opacities(&values)[0].0, 1);
+        assert_eq!(animator.animated_surfaces(AnimatedProperty::Opacity).into_iter().collect::<Vec<_>>(), vec![1]);
+    }
+}
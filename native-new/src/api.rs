@@ -14,6 +14,9 @@ pub trait App {
     fn create_window(&mut self) -> WindowId;
     fn get_window_mut(&mut self, id: WindowId) -> &mut Window;
     fn destroy_window(&mut self, id: WindowId);
+
+    // text layout without any window/surface
+    fn measure_text(&mut self, text: &Text, max_width: Option<f32>) -> LaidText;
 }
 
 pub use crate::generated::{Event, WindowEvent};
@@ -25,6 +28,9 @@ pub trait Window {
     fn scene_mut(&mut self) -> &mut Scene;
     fn render(&mut self);
 
+    // runs natively, without any further messages from JS
+    fn animate(&mut self, animation: Animation);
+
     // platform-specific (and optional)
     fn set_size(&mut self, _width: i32, _height: i32) {}
     fn set_title(&mut self, _title: &str) {}
@@ -53,37 +59,70 @@ pub trait Scene {
 
     // layout props
     fn set_size(&mut self, surface: SurfaceId, size: Size);
+    fn set_min_size(&mut self, surface: SurfaceId, size: Size);
+    fn set_max_size(&mut self, surface: SurfaceId, size: Size);
+    fn set_aspect_ratio(&mut self, surface: SurfaceId, aspect_ratio: Option<f32>);
     fn set_flex(&mut self, surface: SurfaceId, flex: Flex);
     fn set_flow(&mut self, surface: SurfaceId, flow: Flow);
     fn set_padding(&mut self, surface: SurfaceId, padding: Dimensions);
     fn set_margin(&mut self, surface: SurfaceId, margin: Dimensions);
+    fn position(&self, surface: SurfaceId) -> Option<&Position>;
+    fn set_position(&mut self, surface: SurfaceId, position: Position);
+    fn display(&self, surface: SurfaceId) -> Display;
+    fn set_display(&mut self, surface: SurfaceId, display: Display);
 
     // layout info
     fn computed_layout(&self, surface: SurfaceId) -> Rect;
-    fn text_layout(&self, surface: SurfaceId) -> LaidText;
+    // None if there's no (laid out) text, ie. under display: none
+    fn text_layout(&self, surface: SurfaceId) -> Option<LaidText>;
+    // relative to the surface, offsets are in chars
+    fn text_offset_at(&self, surface: SurfaceId, x: f32, y: f32) -> Option<usize>;
+    fn caret_rect(&self, surface: SurfaceId, offset: usize) -> Option<Rect>;
     fn scroll_frame(&self, surface: SurfaceId) -> Option<(f32, f32)>;
+    // relative to the window (including transforms & scroll offsets of the parents)
+    fn absolute_origin(&self, surface: SurfaceId) -> (f32, f32);
+    // whether the window should send `LayoutChanged` for the surface
+    fn observe_layout(&mut self, surface: SurfaceId, observe: bool);
 
     // layout/visual
     fn border_radius(&self, surface: SurfaceId) -> Option<&BorderRadius>;
     fn set_border_radius(&mut self, surface: SurfaceId, border_radius: Option<BorderRadius>);
+    fn overflow(&self, surface: SurfaceId) -> Overflow;
     fn set_overflow(&mut self, surface: SurfaceId, overflow: Overflow);
 
     // visual props
-    fn box_shadow(&self, surface: SurfaceId) -> Option<&BoxShadow>;
-    fn set_box_shadow(&mut self, surface: SurfaceId, box_shadow: Option<BoxShadow>);
+    fn opacity(&self, surface: SurfaceId) -> Option<f32>;
+    fn set_opacity(&mut self, surface: SurfaceId, opacity: f32);
+    fn visibility(&self, surface: SurfaceId) -> Option<&Visibility>;
+    fn set_visibility(&mut self, surface: SurfaceId, visibility: Option<Visibility>);
+    // resolved visibility (inherited from the closest parent which has it set)
+    fn is_hidden(&self, surface: SurfaceId) -> bool;
+    fn z_index(&self, surface: SurfaceId) -> Option<i32>;
+    fn set_z_index(&mut self, surface: SurfaceId, z_index: i32);
+    fn transform(&self, surface: SurfaceId) -> Option<&Transform>;
+    fn set_transform(&mut self, surface: SurfaceId, transform: Option<Transform>);
+    fn box_shadows(&self, surface: SurfaceId) -> &[BoxShadow];
+    fn set_box_shadows(&mut self, surface: SurfaceId, box_shadows: Vec<BoxShadow>);
     fn background_color(&self, surface: SurfaceId) -> Option<&Color>;
     fn set_background_color(&mut self, surface: SurfaceId, color: Option<Color>);
+    fn background(&self, surface: SurfaceId) -> Option<&Background>;
+    fn set_background(&mut self, surface: SurfaceId, background: Option<Background>);
     fn image(&self, surface: SurfaceId) -> Option<&Image>;
     fn set_image(&mut self, surface: SurfaceId, image: Option<Image>);
+    fn svg(&self, surface: SurfaceId) -> Option<&Svg>;
+    fn set_svg(&mut self, surface: SurfaceId, svg: Option<Svg>);
     fn text(&self, surface: SurfaceId) -> Option<&Text>;
     fn set_text(&mut self, surface: SurfaceId, text: Option<Text>);
+    fn text_input(&self, surface: SurfaceId) -> Option<&TextInputState>;
+    fn set_text_input(&mut self, surface: SurfaceId, text_input: Option<TextInput>);
     fn border(&self, surface: SurfaceId) -> Option<&Border>;
     fn set_border(&mut self, surface: SurfaceId, border: Option<Border>);
 }
 
 // re-export some value objects
 pub use crate::generated::{
-    Border, BorderRadius, BorderSide, BorderStyle, BoxShadow, Color, Dimension, Dimensions, Flex, Flow, Image,
-    Rect, Size, SurfaceId, Text, TextAlign, WindowId, FlexAlign, FlexDirection, FlexWrap, JustifyContent, Overflow
+    Background, Border, BorderRadius, CornerRadius, BorderSide, BorderStyle, BoxShadow, Color, ColorStop, ConicGradient, Dimension, Dimensions, Flex, Flow, Image, LinearGradient, Matrix, RadialGradient,
+    Rect, Size, SurfaceId, Svg, Text, TextInput, TextAlign, TextSpan, TextDecoration, TextDecorationStyle, TextShadow, FontStyle, FontFeature, FontVariation, Ellipsize, WhiteSpace, WordBreak, Transform, TransformOp, TransformOrigin, WindowId, FlexAlign, FlexDirection, FlexWrap, JustifyContent, Direction, Overflow, Position, PositionType, Display, Visibility
 };
-use crate::text::LaidText;
+pub use crate::animation::Animation;
+use crate::text::{LaidText, TextInputState};
@@ -1,5 +1,6 @@
-use crate::api::{App, Event, Window, WindowId};
+use crate::api::{App, Event, Text, Window, WindowId};
 use crate::window::AppWindow;
+use crate::text::{LaidText, PangoService, TextLayoutAlgo};
 use glfw::{Context, Glfw, WindowEvent};
 use std::collections::BTreeMap;
 use std::sync::mpsc::Receiver;
@@ -8,6 +9,7 @@ pub struct TheApp {
     glfw: Glfw,
     windows: BTreeMap<WindowId, (AppWindow, Receiver<(f64, WindowEvent)>)>,
     next_window_id: WindowId,
+    text_layout_algo: PangoService,
 }
 
 impl TheApp {
@@ -24,29 +26,47 @@ impl TheApp {
             glfw,
             windows: BTreeMap::new(),
             next_window_id: 1,
+            text_layout_algo: PangoService::new(),
         }
     }
 }
 
 impl App for TheApp {
     fn get_events(&mut self, poll: bool) -> Vec<Event> {
+        let animating = self.windows.values().any(|(window, _)| window.is_animating());
+
         if poll {
             self.glfw.poll_events()
+        } else if animating {
+            // wait at most one frame
+            self.glfw.wait_events_timeout(FRAME_TIME);
         } else {
             // wait a bit otherwise (save battery)
             self.glfw.wait_events_timeout(0.1);
         }
 
-        // go through all windows, handle their events, collect all the resulting events and wrap them along with respective window_id
+        // go through all windows, handle their events, advance their animations,
+        // collect all the resulting events and wrap them along with respective window_id
         self.windows
             .iter_mut()
             .flat_map(|(id, (window, events))| {
-                glfw::flush_messages(events)
-                    .filter_map(move |(_, e)| window.handle_event(e))
-                    .map(move |e| Event::WindowEvent {
-                        window: *id,
-                        event: e,
-                    })
+                let mut window_events: Vec<_> = glfw::flush_messages(events)
+                    .filter_map(|(_, e)| window.handle_event(e))
+                    .collect();
+
+                if window.is_animating() {
+                    window_events.extend(window.tick());
+                }
+
+                window.update_caret();
+                window_events.extend(window.take_layout_events());
+
+                let id = *id;
+
+                window_events.into_iter().map(move |e| Event::WindowEvent {
+                    window: id,
+                    event: e,
+                })
             })
             .collect()
     }
@@ -80,4 +100,11 @@ impl App for TheApp {
     fn destroy_window(&mut self, id: WindowId) {
         self.windows.remove(&id);
     }
+
+    fn measure_text(&mut self, text: &Text, max_width: Option<f32>) -> LaidText {
+        self.text_layout_algo.layout_text(text, max_width)
+    }
 }
+
+// 60fps
+static FRAME_TIME: f64 = 1. / 60.;
@@ -1,12 +1,14 @@
-use crate::api::App;
+use crate::api::{Animation, App};
 use crate::app::TheApp;
-use crate::generated::{FfiMsg, FfiResult, UpdateSceneMsg};
-use bincode::{deserialize, serialize, serialize_into};
+use crate::generated::{FfiMsg, FfiResult, Rect, SurfaceLayout, UpdateSceneMsg};
+use bincode::{deserialize, serialize};
 use serde_json;
-use std::io::prelude::Write;
 
 static mut APP: Option<TheApp> = None;
 
+// serialized result which didn't fit into the buffer (see `take_result`)
+static mut PENDING_RESULT: Option<Vec<u8>> = None;
+
 #[no_mangle]
 pub extern "C" fn init() {
     env_logger::init();
@@ -22,6 +24,8 @@ pub extern "C" fn init() {
 // - bincode encoding does not necessarily have to slow, it depends on the
 //   shape of the result
 // - often-occurring results should be "small" (Nothing, MouseMove)
+// - results which don't fit (long text, many glyphs/layouts) are kept
+//   and only their length is sent (as `Pending`), js then calls `take_result`
 #[no_mangle]
 pub extern "C" fn send(data: *const u8, len: u32, result_ptr: *mut u8) {
     // get slice of bytes & try to deserialize
@@ -45,20 +49,40 @@ pub extern "C" fn send(data: *const u8, len: u32, result_ptr: *mut u8) {
         FfiResult::Nothing
     });
 
-    // serialize & write the result
-    unsafe {
-        // TODO: find a way to avoid memcpy
-        // (it's not possible to use to_writer, because it takes ownership and so it would free the vec & the data)
-        // let data = serde_json::to_vec(&result).expect("couldn't serialize result");
-        let data = serialize(&result).expect("couldn't serialize result");
-        let mut writer = Vec::from_raw_parts(result_ptr, 0, 1024);
-        writer.write(&data[..]).unwrap();
-
-        // serialize_into(writer, &result).unwrap();
-        Box::leak(Box::new(writer));
+    write_or_keep_result(&result, result_ptr);
+}
+
+// serialize & write the result (or keep it if it's too big)
+fn write_or_keep_result(result: &FfiResult, result_ptr: *mut u8) {
+    // let data = serde_json::to_vec(&result).expect("couldn't serialize result");
+    let mut data = serialize(result).expect("couldn't serialize result");
+
+    if data.len() > RESULT_BUF_SIZE {
+        let pending = FfiResult::Pending(data.len() as u32);
+
+        unsafe { PENDING_RESULT = Some(data) }
+        data = serialize(&pending).expect("couldn't serialize result");
+    }
+
+    unsafe { write_result(&data, result_ptr) }
+}
+
+/// Copy the pending result to the buffer (which has to be big enough)
+#[no_mangle]
+pub extern "C" fn take_result(result_ptr: *mut u8) {
+    let data = unsafe { PENDING_RESULT.take() };
+
+    match data {
+        Some(data) => unsafe { write_result(&data, result_ptr) },
+        None => error!("there's no pending result"),
     }
 }
 
+// the memory is owned by js so we can only copy into it
+unsafe fn write_result(data: &[u8], result_ptr: *mut u8) {
+    std::ptr::copy_nonoverlapping(data.as_ptr(), result_ptr, data.len());
+}
+
 fn handle_msg(app: &mut TheApp, msg: FfiMsg) -> FfiResult {
     match msg {
         FfiMsg::GetEvents(poll) => FfiResult::Events(app.get_events(poll)),
@@ -98,6 +122,19 @@ fn handle_msg(app: &mut TheApp, msg: FfiMsg) -> FfiResult {
                         ctx.set_overflow(surface, overflow)
                     }
                     UpdateSceneMsg::SetSize { surface, size } => ctx.set_size(surface, size),
+                    UpdateSceneMsg::SetMinSize { surface, size } => ctx.set_min_size(surface, size),
+                    UpdateSceneMsg::SetMaxSize { surface, size } => ctx.set_max_size(surface, size),
+                    UpdateSceneMsg::SetAspectRatio {
+                        surface,
+                        aspect_ratio,
+                    } => ctx.set_aspect_ratio(surface, aspect_ratio),
+                    UpdateSceneMsg::SetDisplay { surface, display } => {
+                        ctx.set_display(surface, display)
+                    }
+                    UpdateSceneMsg::SetVisibility {
+                        surface,
+                        visibility,
+                    } => ctx.set_visibility(surface, visibility),
                     UpdateSceneMsg::SetFlow { surface, flow } => ctx.set_flow(surface, flow),
                     UpdateSceneMsg::SetFlex { surface, flex } => ctx.set_flex(surface, flex),
                     UpdateSceneMsg::SetPadding { surface, padding } => {
@@ -106,23 +143,158 @@ fn handle_msg(app: &mut TheApp, msg: FfiMsg) -> FfiResult {
                     UpdateSceneMsg::SetMargin { surface, margin } => {
                         ctx.set_margin(surface, margin)
                     }
-                    UpdateSceneMsg::SetBoxShadow {
+                    UpdateSceneMsg::SetBoxShadows {
                         surface,
-                        box_shadow,
-                    } => ctx.set_box_shadow(surface, box_shadow),
+                        box_shadows,
+                    } => ctx.set_box_shadows(surface, box_shadows),
                     UpdateSceneMsg::SetBackgroundColor { surface, color } => {
                         ctx.set_background_color(surface, color)
                     }
                     UpdateSceneMsg::SetImage { surface, image } => ctx.set_image(surface, image),
                     UpdateSceneMsg::SetText { surface, text } => ctx.set_text(surface, text),
+                    UpdateSceneMsg::SetTextInput { surface, text_input } => {
+                        ctx.set_text_input(surface, text_input)
+                    }
                     UpdateSceneMsg::SetBorder { surface, border } => {
                         ctx.set_border(surface, border)
                     }
+                    UpdateSceneMsg::SetSvg { surface, svg } => ctx.set_svg(surface, svg),
+                    UpdateSceneMsg::SetOpacity { surface, opacity } => {
+                        ctx.set_opacity(surface, opacity)
+                    }
+                    UpdateSceneMsg::SetZIndex { surface, z_index } => {
+                        ctx.set_z_index(surface, z_index)
+                    }
+                    UpdateSceneMsg::SetTransform { surface, transform } => {
+                        ctx.set_transform(surface, transform)
+                    }
+                    UpdateSceneMsg::SetBackground {
+                        surface,
+                        background,
+                    } => ctx.set_background(surface, background),
+                    UpdateSceneMsg::ObserveLayout { surface, observe } => {
+                        ctx.observe_layout(surface, observe)
+                    }
+                    UpdateSceneMsg::SetPosition { surface, position } => {
+                        ctx.set_position(surface, position)
+                    }
                 }
             }
 
             window.render();
             FfiResult::Nothing
         }
+        FfiMsg::Animate {
+            window,
+            surface,
+            property,
+            from,
+            to,
+            duration,
+            easing,
+            delay,
+            iterations,
+        } => {
+            let animation = Animation {
+                surface,
+                property,
+                from,
+                to,
+                duration,
+                easing,
+                delay,
+                iterations,
+            };
+
+            // rejected here so it can't break the other animations later
+            match animation.is_valid() {
+                true => app.get_window_mut(window).animate(animation),
+                false => error!("invalid animation {:?}", animation),
+            }
+
+            FfiResult::Nothing
+        }
+        FfiMsg::TextOffsetAt { window, surface, x, y } => {
+            // nothing if there's no text (or it's not laid out)
+            match app.get_window_mut(window).scene_mut().text_offset_at(surface, x, y) {
+                Some(offset) => FfiResult::TextOffset(offset as u32),
+                None => FfiResult::Nothing,
+            }
+        }
+        FfiMsg::CaretRect { window, surface, offset } => {
+            match app.get_window_mut(window).scene_mut().caret_rect(surface, offset as usize) {
+                Some(rect) => FfiResult::Rect(rect),
+                None => FfiResult::Nothing,
+            }
+        }
+        FfiMsg::MeasureText { text, max_width } => FfiResult::TextLayout(app.measure_text(&text, max_width).into()),
+        FfiMsg::GetTextLayout { window, surface } => {
+            match app.get_window_mut(window).scene_mut().text_layout(surface) {
+                Some(laid_text) => FfiResult::TextLayout(laid_text.into()),
+                None => FfiResult::Nothing,
+            }
+        }
+        FfiMsg::GetLayout { window, surfaces } => {
+            let scene = app.get_window_mut(window).scene_mut();
+
+            FfiResult::Layouts(
+                surfaces
+                    .into_iter()
+                    .map(|surface| {
+                        let rect = scene.computed_layout(surface);
+                        let (x, y) = scene.absolute_origin(surface);
+
+                        SurfaceLayout { absolute: Rect(x, y, rect.2, rect.3), rect }
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+// allocated by js, anything bigger is sent in two steps (see `send`)
+static RESULT_BUF_SIZE: usize = 1024;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_result() {
+        let mut buf = vec![0u8; RESULT_BUF_SIZE];
+
+        // small results are written directly
+        write_or_keep_result(&FfiResult::WindowId(1), buf.as_mut_ptr());
+
+        match deserialize(&buf).unwrap() {
+            FfiResult::WindowId(1) => {}
+            res => panic!("unexpected {:?}", res),
+        }
+
+        let layout = SurfaceLayout { rect: Rect(0., 0., 10., 10.), absolute: Rect(5., 5., 10., 10.) };
+        let layouts = FfiResult::Layouts(vec![layout; 100]);
+
+        write_or_keep_result(&layouts, buf.as_mut_ptr());
+
+        let len = match deserialize(&buf).unwrap() {
+            FfiResult::Pending(len) => len as usize,
+            res => panic!("unexpected {:?}", res),
+        };
+
+        assert!(len > RESULT_BUF_SIZE);
+
+        let mut pending_buf = vec![0u8; len];
+        take_result(pending_buf.as_mut_ptr());
+
+        match deserialize(&pending_buf).unwrap() {
+            FfiResult::Layouts(layouts) => {
+                assert_eq!(layouts.len(), 100);
+                assert_eq!(layouts[99].absolute.0, 5.);
+            }
+            res => panic!("unexpected {:?}", res),
+        }
+
+        // taken only once
+        assert!(unsafe { PENDING_RESULT.is_none() });
     }
 }
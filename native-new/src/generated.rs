@@ -7,6 +7,12 @@ pub enum FfiMsg {
     GetEvents(bool),
     CreateWindow,
     UpdateScene { window: WindowId, msgs: Vec<UpdateSceneMsg> },
+    Animate { window: WindowId, surface: SurfaceId, property: AnimatedProperty, from: AnimatedValue, to: AnimatedValue, duration: f32, easing: Easing, delay: f32, iterations: u32 },
+    TextOffsetAt { window: WindowId, surface: SurfaceId, x: f32, y: f32 },
+    CaretRect { window: WindowId, surface: SurfaceId, offset: u32 },
+    MeasureText { text: Text, #[serde(rename = "maxWidth")] max_width: Option<f32> },
+    GetTextLayout { window: WindowId, surface: SurfaceId },
+    GetLayout { window: WindowId, surfaces: Vec<SurfaceId> },
 }
 
 
@@ -15,6 +21,11 @@ pub enum FfiResult {
     Nothing,
     Events(Vec<Event>),
     WindowId(WindowId),
+    TextOffset(u32),
+    Rect(Rect),
+    TextLayout(TextLayout),
+    Layouts(Vec<SurfaceLayout>),
+    Pending(u32),
 }
 
 
@@ -26,9 +37,9 @@ pub enum Event {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum WindowEvent {
-    MouseMove { target: usize },
-    MouseDown { target: usize },
-    MouseUp { target: usize },
+    MouseMove { target: usize, link: Option<u32> },
+    MouseDown { target: usize, link: Option<u32> },
+    MouseUp { target: usize, link: Option<u32> },
     Scroll { target: usize },
     KeyDown(u16),
     KeyPress(u16),
@@ -38,6 +49,9 @@ pub enum WindowEvent {
     Resize,
     Close,
     Unknown,
+    AnimationEnd { target: usize, property: AnimatedProperty },
+    TextChanged { target: usize, value: String },
+    LayoutChanged { target: usize, rect: Rect },
 }
 
 
@@ -54,11 +68,24 @@ pub enum UpdateSceneMsg {
     SetFlow { surface: SurfaceId, flow: Flow },
     SetPadding { surface: SurfaceId, padding: Dimensions },
     SetMargin { surface: SurfaceId, margin: Dimensions },
-    SetBoxShadow { surface: SurfaceId, #[serde(rename = "boxShadow")] box_shadow: Option<BoxShadow> },
+    SetBoxShadows { surface: SurfaceId, #[serde(rename = "boxShadows")] box_shadows: Vec<BoxShadow> },
     SetBackgroundColor { surface: SurfaceId, color: Option<Color> },
     SetImage { surface: SurfaceId, image: Option<Image> },
     SetText { surface: SurfaceId, text: Option<Text> },
     SetBorder { surface: SurfaceId, border: Option<Border> },
+    SetSvg { surface: SurfaceId, svg: Option<Svg> },
+    SetOpacity { surface: SurfaceId, opacity: f32 },
+    SetZIndex { surface: SurfaceId, #[serde(rename = "zIndex")] z_index: i32 },
+    SetTransform { surface: SurfaceId, transform: Option<Transform> },
+    SetBackground { surface: SurfaceId, background: Option<Background> },
+    SetTextInput { surface: SurfaceId, #[serde(rename = "textInput")] text_input: Option<TextInput> },
+    ObserveLayout { surface: SurfaceId, observe: bool },
+    SetPosition { surface: SurfaceId, position: Position },
+    SetMinSize { surface: SurfaceId, size: Size },
+    SetMaxSize { surface: SurfaceId, size: Size },
+    SetAspectRatio { surface: SurfaceId, #[serde(rename = "aspectRatio")] aspect_ratio: Option<f32> },
+    SetDisplay { surface: SurfaceId, display: Display },
+    SetVisibility { surface: SurfaceId, visibility: Option<Visibility> },
 }
 
 
@@ -68,6 +95,33 @@ pub type WindowId = u16;
 pub type SurfaceId = usize;
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum AnimatedProperty {
+    Opacity,
+    Transform,
+    BackgroundColor,
+    ScrollOffset,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum AnimatedValue {
+    Opacity(f32),
+    Transform(Transform),
+    BackgroundColor(Color),
+    ScrollOffset(Vector2f),
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Color(pub u8, pub u8, pub u8, pub u8);
 
@@ -113,6 +167,14 @@ pub enum JustifyContent {
 }
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Direction {
+    Inherit,
+    Ltr,
+    Rtl,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Flow {
     #[serde(rename = "flexDirection")]
@@ -133,6 +195,7 @@ pub struct Flow {
     #[serde(rename = "justifyContent")]
     pub justify_content: JustifyContent,
 
+    pub direction: Direction,
 }
 
 
@@ -166,6 +229,37 @@ pub enum Overflow {
 }
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum PositionType {
+    Relative,
+    Absolute,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Position {
+    pub kind: PositionType,
+    pub top: Dimension,
+    pub right: Dimension,
+    pub bottom: Dimension,
+    pub left: Dimension,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Display {
+    Flex,
+    None,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Visibility {
+    Visible,
+    Hidden,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Size(pub Dimension, pub Dimension);
 
@@ -174,6 +268,13 @@ pub struct Size(pub Dimension, pub Dimension);
 pub struct Rect(pub f32, pub f32, pub f32, pub f32);
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SurfaceLayout {
+    pub rect: Rect,
+    pub absolute: Rect,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Dimensions(pub Dimension, pub Dimension, pub Dimension, pub Dimension);
 
@@ -183,7 +284,11 @@ pub struct Vector2f(pub f32, pub f32);
 
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct BorderRadius(pub f32, pub f32, pub f32, pub f32);
+pub struct CornerRadius(pub Dimension, pub Dimension);
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BorderRadius(pub CornerRadius, pub CornerRadius, pub CornerRadius, pub CornerRadius);
 
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -192,6 +297,70 @@ pub struct BoxShadow {
     pub offset: Vector2f,
     pub blur: f32,
     pub spread: f32,
+    pub inset: bool,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Matrix(pub f32, pub f32, pub f32, pub f32, pub f32, pub f32);
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum TransformOp {
+    Matrix(Matrix),
+    Translate(Vector2f),
+    Scale(Vector2f),
+    Rotate(f32),
+    Skew(Vector2f),
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TransformOrigin(pub Dimension, pub Dimension);
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Transform {
+    pub ops: Vec<TransformOp>,
+    pub origin: TransformOrigin,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LinearGradient {
+    pub angle: f32,
+    pub stops: Vec<ColorStop>,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RadialGradient {
+    pub center: Vector2f,
+    pub radius: Vector2f,
+    pub stops: Vec<ColorStop>,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ConicGradient {
+    pub center: Vector2f,
+    pub angle: f32,
+    pub stops: Vec<ColorStop>,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Background {
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
 }
 
 
@@ -201,11 +370,116 @@ pub struct Image {
 }
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Svg {
+    Source(String),
+    Path(String),
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum TextAlign {
     Left,
     Center,
     Right,
+    Justify,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Ellipsize {
+    None,
+    Start,
+    Middle,
+    End,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum WhiteSpace {
+    Normal,
+    NoWrap,
+    Pre,
+    PreWrap,
+    PreLine,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum WordBreak {
+    Normal,
+    BreakAll,
+    BreakWord,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextSpan {
+    pub start: u32,
+    pub end: u32,
+    pub color: Option<Color>,
+    #[serde(rename = "fontSize")]
+    pub font_size: Option<f32>,
+
+    #[serde(rename = "fontWeight")]
+    pub font_weight: Option<u32>,
+
+    #[serde(rename = "fontStyle")]
+    pub font_style: Option<FontStyle>,
+
+    pub underline: bool,
+    pub link: Option<u32>,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum TextDecorationStyle {
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextDecoration {
+    pub underline: bool,
+    #[serde(rename = "lineThrough")]
+    pub line_through: bool,
+
+    pub overline: bool,
+    pub color: Option<Color>,
+    pub style: TextDecorationStyle,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextShadow {
+    pub offset: Vector2f,
+    pub blur: f32,
+    pub color: Color,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FontFeature {
+    pub tag: String,
+    pub value: u32,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FontVariation {
+    pub tag: String,
+    pub value: f32,
 }
 
 
@@ -219,16 +493,82 @@ pub struct Text {
     pub line_height: f32,
 
     pub align: TextAlign,
+    #[serde(rename = "numberOfLines")]
+    pub number_of_lines: u32,
+
+    pub ellipsize: Ellipsize,
+    #[serde(rename = "whiteSpace")]
+    pub white_space: WhiteSpace,
+
+    #[serde(rename = "wordBreak")]
+    pub word_break: WordBreak,
+
+    pub spans: Vec<TextSpan>,
+    pub decoration: Option<TextDecoration>,
+    pub shadows: Vec<TextShadow>,
+    #[serde(rename = "letterSpacing")]
+    pub letter_spacing: f32,
+
+    #[serde(rename = "fontFeatures")]
+    pub font_features: Vec<FontFeature>,
+
+    #[serde(rename = "fontVariations")]
+    pub font_variations: Vec<FontVariation>,
+
+    pub direction: Direction,
     pub text: String,
 }
 
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextInput {
+    pub value: String,
+    pub placeholder: String,
+    #[serde(rename = "placeholderColor")]
+    pub placeholder_color: Option<Color>,
+
+    pub secure: bool,
+    pub multiline: bool,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextLine {
+    pub rect: Rect,
+    pub start: usize,
+    pub baseline: f32,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GlyphBox {
+    pub rect: Rect,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TextLayout {
+    pub width: f32,
+    pub height: f32,
+    pub baseline: f32,
+    pub ascent: f32,
+    pub descent: f32,
+    pub truncated: bool,
+    pub lines: Vec<TextLine>,
+    pub glyphs: Vec<GlyphBox>,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Border {
     pub top: BorderSide,
     pub right: BorderSide,
     pub bottom: BorderSide,
     pub left: BorderSide,
+    pub image: Option<BorderImage>,
 }
 
 
@@ -244,5 +584,42 @@ pub struct BorderSide {
 pub enum BorderStyle {
     None,
     Solid,
+    Dashed,
+    Dotted,
+    Double,
+    Groove,
+    Ridge,
+    Inset,
+    Outset,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BorderImage {
+    pub source: BorderImageSource,
+    pub slice: BorderImageSlice,
+    pub fill: bool,
+    pub repeat: BorderImageRepeat,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum BorderImageSource {
+    Image(Image),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BorderImageSlice(pub u32, pub u32, pub u32, pub u32);
+
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum BorderImageRepeat {
+    Stretch,
+    Repeat,
+    Round,
+    Space,
 }
 
@@ -1,4 +1,4 @@
-use crate::api::{SurfaceId, Rect, Size, Flex, Flow, Dimensions, Text, Overflow, Border};
+use crate::api::{SurfaceId, Rect, Size, Flex, Flow, Dimensions, Text, Svg, Overflow, Border, Position, Display};
 use crate::text::LaidText;
 
 /// Tree of layout nodes along with respective calculations
@@ -15,16 +15,23 @@ pub trait LayoutTree {
     fn insert_at(&mut self, parent: NodeId, child: NodeId, index: u32);
 
     fn set_size(&mut self, node_id: NodeId, size: Size);
+    fn set_min_size(&mut self, node_id: NodeId, size: Size);
+    fn set_max_size(&mut self, node_id: NodeId, size: Size);
+    fn set_aspect_ratio(&mut self, node_id: NodeId, aspect_ratio: Option<f32>);
     fn set_flex(&mut self, node_id: NodeId, flex: Flex);
     fn set_flow(&mut self, node_id: NodeId, flow: Flow);
     fn set_padding(&mut self, node_id: NodeId, padding: Dimensions);
     fn set_border(&mut self, node_id: NodeId, border: Option<Border>);
     fn set_margin(&mut self, node_id: NodeId, margin: Dimensions);
+    fn set_position(&mut self, node_id: NodeId, position: Position);
+    fn set_display(&mut self, node_id: NodeId, display: Display);
     fn set_text(&mut self, node_id: NodeId, text: Option<Text>);
+    fn set_svg(&mut self, node_id: NodeId, svg: Option<Svg>);
 
     fn calculate(&mut self);
     fn computed_layout(&self, node_id: NodeId) -> Rect;
-    fn text_layout(&self, node_id: NodeId) -> LaidText;
+    // None if there's no text or it was not laid out (display: none)
+    fn text_layout(&self, node_id: NodeId) -> Option<LaidText>;
     fn set_overflow(&mut self, node_id: NodeId, overflow: Overflow);
     fn scroll_frame(&self, surface: SurfaceId) -> Option<(f32, f32)>;
 }
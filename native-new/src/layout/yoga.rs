@@ -2,24 +2,28 @@
 use ordered_float::OrderedFloat;
 use std::f32;
 use yoga::{
-    Align, Context, Direction, FlexDirection as YogaFlexDirection, FlexStyle, MeasureMode,
-    Node as YogaNode, NodeRef, StyleUnit, Wrap,
+    Align, Context, Direction as YogaDirection, Display as YogaDisplay, FlexDirection as YogaFlexDirection, FlexStyle, MeasureMode,
+    Node as YogaNode, NodeRef, PositionType as YogaPositionType, StyleUnit, Wrap,
 };
 
 use super::LayoutTree;
 use crate::api::{
-    Rect, Dimension, Dimensions, Flex, FlexAlign, FlexDirection, FlexWrap, Flow, JustifyContent,
-    Size, Text, Overflow, Border
+    Rect, Dimension, Dimensions, Direction, Flex, FlexAlign, FlexDirection, FlexWrap, Flow, JustifyContent,
+    Size, Svg, Text, Overflow, Border, Position, PositionType, Display
 };
 use crate::text::{PangoService, TextLayoutAlgo, LaidText};
+use crate::svg;
 use crate::Id;
 use yoga::types::Justify;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub struct YogaTree {
     yoga_nodes: Vec<YogaNode>,
     text_layout_algo: PangoService,
-    text_layouts: BTreeMap<Id, LaidText>
+    text_layouts: BTreeMap<Id, LaidText>,
+    // text & svg share the measure func (the last one set wins),
+    // so we need to know which one can be removed
+    svgs: BTreeSet<Id>
 }
 
 impl YogaTree {
@@ -27,7 +31,8 @@ impl YogaTree {
         YogaTree {
             yoga_nodes: vec![],
             text_layout_algo: PangoService::new(),
-            text_layouts: BTreeMap::new()
+            text_layouts: BTreeMap::new(),
+            svgs: BTreeSet::new()
         }
     }
 }
@@ -64,6 +69,27 @@ impl LayoutTree for YogaTree {
         ])
     }
 
+    fn set_min_size(&mut self, id: Id, size: Size) {
+        self.yoga_nodes[id].apply_styles(&[
+            FlexStyle::MinWidth(size.0.into()),
+            FlexStyle::MinHeight(size.1.into()),
+        ])
+    }
+
+    fn set_max_size(&mut self, id: Id, size: Size) {
+        self.yoga_nodes[id].apply_styles(&[
+            FlexStyle::MaxWidth(size.0.into()),
+            FlexStyle::MaxHeight(size.1.into()),
+        ])
+    }
+
+    // NaN is undefined for yoga
+    fn set_aspect_ratio(&mut self, id: Id, aspect_ratio: Option<f32>) {
+        self.yoga_nodes[id].apply_styles(&[
+            FlexStyle::AspectRatio(aspect_ratio.unwrap_or(f32::NAN).into()),
+        ])
+    }
+
     fn set_flex(&mut self, id: Id, flex: Flex) {
         self.yoga_nodes[id].apply_styles(&[
             FlexStyle::FlexGrow(flex.flex_grow.into()),
@@ -81,6 +107,7 @@ impl LayoutTree for YogaTree {
             FlexStyle::AlignItems(flow.align_items.into()),
             FlexStyle::AlignSelf(flow.align_self.into()),
         ]);
+        self.yoga_nodes[id].set_direction(flow.direction.into());
     }
 
     fn set_padding(&mut self, id: Id, padding: Dimensions) {
@@ -112,6 +139,23 @@ impl LayoutTree for YogaTree {
         ]);
     }
 
+    // offsets are relative to the padding box of the parent (for absolute) or to the normal position
+    fn set_position(&mut self, id: Id, position: Position) {
+        self.yoga_nodes[id].apply_styles(&[
+            FlexStyle::Position(position.kind.into()),
+            FlexStyle::Top(position.top.into()),
+            FlexStyle::Right(position.right.into()),
+            FlexStyle::Bottom(position.bottom.into()),
+            FlexStyle::Left(position.left.into()),
+        ]);
+    }
+
+    fn set_display(&mut self, id: Id, display: Display) {
+        self.yoga_nodes[id].apply_styles(&[
+            FlexStyle::Display(display.into()),
+        ]);
+    }
+
     fn set_text<'svc>(&mut self, id: Id, text: Option<Text>) {
         // yoganode context has static lifetime and we need to access pango and text_layouts somehow
         // should be safe but I might be wrong OFC
@@ -123,15 +167,36 @@ impl LayoutTree for YogaTree {
             node.set_measure_func(Some(measure_text_node));
             node.mark_dirty();
             node.set_context(Some(Context::new(MeasureContext(tree_ref, id, text))));
+            self.svgs.remove(&id);
         } else {
+            if !self.svgs.contains(&id) {
+                node.set_measure_func(None);
+                node.set_context(None);
+            }
+
+            self.text_layouts.remove(&id);
+        }
+    }
+
+    fn set_svg(&mut self, id: Id, svg: Option<Svg>) {
+        let node = &mut self.yoga_nodes[id];
+
+        if let Some(svg) = svg {
+            let (width, height) = svg::intrinsic_size(&svg);
+
+            node.set_measure_func(Some(measure_svg_node));
+            node.mark_dirty();
+            node.set_context(Some(Context::new(SvgMeasureContext(width, height))));
+            self.svgs.insert(id);
+        } else if self.svgs.remove(&id) {
             node.set_measure_func(None);
             node.set_context(None);
-            self.text_layouts.remove(&id);
         }
     }
 
     fn calculate(&mut self) {
-        self.yoga_nodes[0].calculate_layout(f32::MAX, f32::MAX, Direction::LTR);
+        // root can have its own direction, otherwise yoga falls back to LTR
+        self.yoga_nodes[0].calculate_layout(f32::MAX, f32::MAX, YogaDirection::Inherit);
     }
 
     fn computed_layout(&self, id: Id) -> Rect {
@@ -145,8 +210,8 @@ impl LayoutTree for YogaTree {
         )
     }
 
-    fn text_layout(&self, id: Id) -> LaidText {
-        self.text_layouts.get(&id).expect("no text on the surface").clone()
+    fn text_layout(&self, id: Id) -> Option<LaidText> {
+        self.text_layouts.get(&id).cloned()
     }
 
     fn set_overflow(&mut self, id: Id, overflow: Overflow) {
@@ -210,6 +275,46 @@ extern "C" fn measure_text_node(
     size
 }
 
+// keep the aspect ratio if only one side is known
+extern "C" fn measure_svg_node(
+    node_ref: NodeRef,
+    w: f32,
+    wm: MeasureMode,
+    h: f32,
+    hm: MeasureMode,
+) -> yoga::Size {
+    let ctx = YogaNode::get_context_mut(&node_ref).expect("no context found");
+    let SvgMeasureContext(svg_width, svg_height) = ctx
+        .downcast_mut::<SvgMeasureContext>()
+        .expect("not a svg measure context");
+
+    // empty (or invalid) svgs take only what is given
+    if (*svg_width <= 0.) || (*svg_height <= 0.) {
+        let side = |v, mode| match mode {
+            MeasureMode::Exactly => v,
+            _ => 0.,
+        };
+
+        return yoga::Size { width: side(w, wm), height: side(h, hm) };
+    }
+
+    let ratio = *svg_height / *svg_width;
+
+    let size = match (wm, hm) {
+        (MeasureMode::Exactly, MeasureMode::Exactly) => yoga::Size { width: w, height: h },
+        (MeasureMode::Exactly, _) => yoga::Size { width: w, height: w * ratio },
+        (_, MeasureMode::Exactly) => yoga::Size { width: h / ratio, height: h },
+        (MeasureMode::AtMost, _) if w < *svg_width => yoga::Size { width: w, height: w * ratio },
+        _ => yoga::Size { width: *svg_width, height: *svg_height },
+    };
+
+    debug!("measure svg {:?}", &size);
+
+    size
+}
+
+struct SvgMeasureContext(f32, f32);
+
 struct MeasureContext (
     pub &'static mut YogaTree,
     pub Id,
@@ -237,6 +342,16 @@ impl Into<YogaFlexDirection> for FlexDirection {
     }
 }
 
+impl Into<YogaDirection> for Direction {
+    fn into(self) -> YogaDirection {
+        match self {
+            Direction::Inherit => YogaDirection::Inherit,
+            Direction::Ltr => YogaDirection::LTR,
+            Direction::Rtl => YogaDirection::RTL,
+        }
+    }
+}
+
 impl Into<Align> for FlexAlign {
     fn into(self) -> Align {
         match self {
@@ -275,6 +390,24 @@ impl Into<Wrap> for FlexWrap {
     }
 }
 
+impl Into<YogaPositionType> for PositionType {
+    fn into(self) -> YogaPositionType {
+        match self {
+            PositionType::Relative => YogaPositionType::Relative,
+            PositionType::Absolute => YogaPositionType::Absolute,
+        }
+    }
+}
+
+impl Into<YogaDisplay> for Display {
+    fn into(self) -> YogaDisplay {
+        match self {
+            Display::Flex => YogaDisplay::Flex,
+            Display::None => YogaDisplay::None,
+        }
+    }
+}
+
 impl Into<yoga::Overflow> for Overflow {
     fn into(self) -> yoga::Overflow {
         match self {
@@ -346,3 +479,99 @@ mod tests {
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tree(count: usize) -> YogaTree {
+        let mut tree = YogaTree::new();
+
+        for _n in 0..count {
+            tree.alloc();
+        }
+
+        tree.set_size(0, Size(Dimension::Point(100.), Dimension::Point(100.)));
+
+        tree
+    }
+
+    fn layout(tree: &YogaTree, id: Id) -> (f32, f32, f32, f32) {
+        let Rect(x, y, width, height) = tree.computed_layout(id);
+
+        (x, y, width, height)
+    }
+
+    #[test]
+    fn test_layout_set_position() {
+        let mut tree = test_tree(3);
+        let position = |kind, top, left| Position {
+            kind,
+            top: Dimension::Point(top),
+            right: Dimension::Auto,
+            bottom: Dimension::Auto,
+            left: Dimension::Point(left),
+        };
+
+        tree.append_child(0, 1);
+        tree.append_child(0, 2);
+        tree.set_size(1, Size(Dimension::Point(10.), Dimension::Point(10.)));
+        tree.set_size(2, Size(Dimension::Point(10.), Dimension::Point(10.)));
+
+        // relative is offset from the normal position & still takes the space
+        tree.set_position(1, position(PositionType::Relative, 5., 5.));
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (5., 5., 10., 10.));
+        assert_eq!(layout(&tree, 2), (0., 10., 10., 10.));
+
+        // absolute is taken out of the flow
+        tree.set_position(1, position(PositionType::Absolute, 20., 30.));
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (30., 20., 10., 10.));
+        assert_eq!(layout(&tree, 2), (0., 0., 10., 10.));
+
+        // bottom/right are relative to the opposite edges
+        tree.set_position(1, Position {
+            kind: PositionType::Absolute,
+            top: Dimension::Auto,
+            right: Dimension::Point(10.),
+            bottom: Dimension::Point(10.),
+            left: Dimension::Auto,
+        });
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (80., 80., 10., 10.));
+    }
+
+    #[test]
+    fn test_layout_min_max_size() {
+        let mut tree = test_tree(2);
+
+        tree.append_child(0, 1);
+        tree.set_size(1, Size(Dimension::Point(10.), Dimension::Point(80.)));
+        tree.set_min_size(1, Size(Dimension::Point(20.), Dimension::Auto));
+        tree.set_max_size(1, Size(Dimension::Auto, Dimension::Percent(50.)));
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (0., 0., 20., 50.));
+    }
+
+    #[test]
+    fn test_layout_aspect_ratio() {
+        let mut tree = test_tree(2);
+
+        tree.append_child(0, 1);
+        tree.set_size(1, Size(Dimension::Point(40.), Dimension::Auto));
+        tree.set_aspect_ratio(1, Some(2.));
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (0., 0., 40., 20.));
+
+        tree.set_aspect_ratio(1, None);
+        tree.calculate();
+
+        assert_eq!(layout(&tree, 1), (0., 0., 40., 0.));
+    }
+}
@@ -7,6 +7,8 @@ mod window;
 mod scene;
 mod layout;
 mod text;
+mod svg;
+mod animation;
 mod render;
 mod storage;
 mod ffi;
@@ -1,29 +1,39 @@
 use crate::api::{
-    Border, BorderRadius, BorderSide, BorderStyle, BoxShadow, Color, Image,
-    Text, SurfaceId, Scene, Rect
+    Background, Border, BorderImage, BorderImageRepeat, BorderImageSlice, BorderImageSource, BorderRadius, CornerRadius, BorderSide, BorderStyle, BoxShadow, Color, ColorStop, ConicGradient, Image,
+    LinearGradient, RadialGradient, Svg, Text, TextAlign, FontVariation, TextDecorationStyle, TextShadow, SurfaceId, Scene, Rect, Dimension, Overflow, Matrix, Transform, TransformOp, TransformOrigin, Position, PositionType, Display
 };
-use crate::generated::{Vector2f, TextAlign};
+use crate::generated::Vector2f;
 use super::SceneRenderer;
-use crate::text::{LaidGlyph, LaidText};
+use crate::text::{align_offset, LaidFont, LaidGlyph, LaidText};
+use crate::svg;
 use gleam::gl::Gl;
 use image;
-use image::GenericImageView;
+use image::RgbaImage;
 use std::fs::File;
 use std::io::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+use std::time::Instant;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use webrender::api::{
     AddImage, AlphaType, BorderDetails, BorderDisplayItem, BorderRadius as WRBorderRadius,
     BorderSide as WRBorderSide, BorderStyle as WRBorderStyle, BoxShadowClipMode,
     BoxShadowDisplayItem, ColorF, ColorU, DisplayListBuilder, DocumentId, Epoch,
-    FontInstanceKey, GlyphInstance, ImageData, ImageDescriptor,
+    AddFont, AddFontInstance, FontKey, FontInstanceKey, GlyphInstance, ImageData, ImageDescriptor, ImageKey,
     ImageDisplayItem, ImageFormat, ImageRendering, LayoutPrimitiveInfo,
     NormalBorder, PipelineId, RectangleDisplayItem, RenderApi,
     ResourceUpdate, SpaceAndClipInfo, SpecificDisplayItem, TextDisplayItem, Transaction,
     HitTestFlags, ComplexClipRegion, ClipMode, ScrollLocation, RenderNotifier, ScrollSensitivity, ExternalScrollId,
-    units::{LayoutPoint, LayoutSize, LayoutVector2D, WorldPoint, LayoutRect, FramebufferIntSize}
+    FilterOp, PropertyBinding, PropertyBindingKey, TransformStyle, MixBlendMode, RasterSpace, ReferenceFrameKind,
+    DynamicProperties, PropertyValue, ScrollClamping, ItemTag,
+    ExtendMode, GradientDisplayItem, GradientStop, RadialGradientDisplayItem,
+    Gradient, RadialGradient as WRRadialGradient, NinePatchBorder, NinePatchBorderSource, RepeatMode,
+    LineDisplayItem, LineOrientation, LineStyle, Shadow, FontVariation as WRFontVariation,
+    units::{LayoutPoint, LayoutSize, LayoutVector2D, WorldPoint, LayoutRect, LayoutTransform, FramebufferIntSize}
 };
-use webrender::euclid::{TypedSideOffsets2D, TypedSize2D, TypedVector2D};
+use webrender::euclid::{Angle, SideOffsets2D, TypedSideOffsets2D, TypedSize2D, TypedVector2D};
 use webrender::{Renderer, RendererOptions};
 
 pub struct WebrenderRenderer {
@@ -33,20 +43,38 @@ pub struct WebrenderRenderer {
     rx: Receiver<()>,
 
     fb_size: FramebufferIntSize,
-    // so that we can reuse already uploaded images
-    // this can be (periodically) cleaned up by simply going through all keys and
-    // looking what has (not) been used in the last render (and can be evicted)
-    // _uploaded_images: BTreeMap<String, ImageKey>
+    dpi: f32,
+
+    // loaded images (keyed by url) so that we don't upload them in every render
+    images: ImageCache<String>,
+
+    // rasterized svgs & conic gradients, keyed by source hash & size in device pixels
+    rasterized_images: ImageCache<(u64, u32, u32)>,
+
+    // loaded lazily (whatever the layout has used, including fallbacks), keyed by the face
+    // instances are keyed by the face, size (in app units) & variations (tag, value bits)
+    fonts: BTreeMap<FontFace, FontKey>,
+    font_instances: BTreeMap<(FontFace, i32, Vec<(u32, u32)>), FontInstanceKey>,
+
+    // what was bound in the last render (and can be animated without rebuilding the display list)
+    // transforms need the rect too because the origin is relative to it
+    opacity_bindings: BTreeSet<SurfaceId>,
+    transform_bindings: BTreeMap<SurfaceId, LayoutRect>,
+
+    // bound even if they are fully opaque (opacity 1 is not in the scene)
+    // so that the animation doesn't need full render in every frame
+    animated_opacities: BTreeSet<SurfaceId>,
+
+    // scroll frames are not part of the scene so they have to be (re)applied with the next frame
+    pending_scroll_offsets: Vec<(SurfaceId, Vector2f)>,
 }
 
 impl WebrenderRenderer {
     pub fn new(gl: Rc<Gl>, fb_size: (i32, i32)) -> Self {
         let fb_size = FramebufferIntSize::new(fb_size.0, fb_size.1);
-        let (renderer, mut render_api, rx) = Self::init_webrender(gl, fb_size);
+        let (renderer, render_api, rx) = Self::init_webrender(gl, fb_size);
         let document_id = render_api.add_document(fb_size, 0);
 
-        Self::load_fonts(&mut render_api, document_id, &rx);
-
         WebrenderRenderer {
             renderer,
             render_api,
@@ -54,14 +82,42 @@ impl WebrenderRenderer {
             rx,
 
             fb_size,
+            dpi: 1.0,
+
+            images: ImageCache::new(),
+            rasterized_images: ImageCache::new(),
+
+            fonts: BTreeMap::new(),
+            font_instances: BTreeMap::new(),
+
+            opacity_bindings: BTreeSet::new(),
+            transform_bindings: BTreeMap::new(),
+
+            animated_opacities: BTreeSet::new(),
+            pending_scroll_offsets: vec![],
         }
     }
 
-    // not complete (border-radius) but it might be fine for some time
-    pub fn hit_test(&self, x: f32, y: f32) -> Option<SurfaceId> {
+    /// Surfaces which have their opacity animated (and should be always bound)
+    pub fn set_animated_opacities(&mut self, surfaces: BTreeSet<SurfaceId>) {
+        self.animated_opacities = surfaces;
+    }
+
+    // clips (both overflow and border-radius) and transforms are handled by webrender
+    // (point is mapped to the respective space & tested against the whole clip chain)
+    //
+    // returns also the link (text span) if there's any and the point relative to the surface
+    // (every item which can be hit has the rect of its surface, smaller ones are clipped or not hit-testable)
+    pub fn hit_test(&self, x: f32, y: f32) -> Option<(SurfaceId, Option<u32>, (f32, f32))> {
         let res = self.render_api.hit_test(self.document_id, Some(PIPELINE_ID), WorldPoint::new(x, y), HitTestFlags::empty());
 
-        res.items.get(0).map(|item| item.tag.1 as usize)
+        res.items.get(0).map(|item| {
+            let point = item.point_relative_to_item;
+
+            let (surface, link) = hit_target(item.tag);
+
+            (surface, link, (point.x, point.y))
+        })
     }
 
     fn init_webrender(gl: Rc<Gl>, fb_size: FramebufferIntSize) -> (Renderer, RenderApi, Receiver<()>) {
@@ -84,40 +140,20 @@ impl WebrenderRenderer {
         (renderer, render_api, rx)
     }
 
-    fn load_fonts(render_api: &mut RenderApi, document_id: DocumentId, rx: &Receiver<()>) {
-        let property = font_loader::system_fonts::FontPropertyBuilder::new()
-            .family("Arial")
-            .build();
-        let (font, font_index) = font_loader::system_fonts::get(&property).unwrap();
-        let font_key = render_api.generate_font_key();
-
-        let mut tx = Transaction::new();
-        tx.set_root_pipeline(PipelineId::dummy());
-        tx.add_raw_font(font_key, font, font_index as u32);
-
-        // TODO: support any size
-        for font_size in [10, 12, 14, 16, 20, 24, 34, 40, 48, 60, 96].iter() {
-            tx.add_font_instance(
-                FontInstanceKey(font_key.0, *font_size),
-                font_key,
-                app_units::Au::from_px(*font_size as i32),
-                None,
-                None,
-                Vec::new(),
-            );
-        }
-
-        tx.generate_frame();
-        render_api.send_transaction(document_id, tx);
-        rx.recv().ok();
-    }
-
     fn send_frame(&mut self, builder: DisplayListBuilder, viewport_size: LayoutSize) {
         let mut tx = Transaction::new();
 
         // according to https://github.com/servo/webrender/wiki/Path-to-the-Screen
         tx.set_root_pipeline(PIPELINE_ID);
         tx.set_display_list(Epoch(0), None, viewport_size, builder.finalize(), true);
+
+        // values in the new display list are up-to-date so any previously animated values have to go
+        tx.update_dynamic_properties(DynamicProperties { transforms: vec![], floats: vec![] });
+
+        for (surface, offset) in std::mem::replace(&mut self.pending_scroll_offsets, vec![]) {
+            scroll_node(&mut tx, surface, offset);
+        }
+
         tx.generate_frame();
 
         self.send_tx(tx);
@@ -147,8 +183,69 @@ impl WebrenderRenderer {
         self.send_tx(tx);
     }
 
+    /// How much is each (scrolled) scroll frame scrolled
+    /// (webrender offsets are negative, they are added to the content)
+    pub fn scroll_positions(&self) -> Vec<(SurfaceId, (f32, f32))> {
+        self.render_api
+            .get_scroll_node_state(self.document_id)
+            .into_iter()
+            .map(|state| (state.id.0 as SurfaceId, (-state.scroll_offset.x, -state.scroll_offset.y)))
+            .collect()
+    }
+
+    /// Scroll offsets which should be applied with the next full render
+    pub fn queue_scroll_offsets(&mut self, scroll_offsets: &[(SurfaceId, Vector2f)]) {
+        self.pending_scroll_offsets.extend_from_slice(scroll_offsets);
+    }
+
+    /// Update animated values without rebuilding the display list
+    ///
+    /// Returns false if some of the surfaces were not bound in the last render,
+    /// full render is needed then (scroll offsets are kept and applied with it)
+    pub fn update_properties(
+        &mut self,
+        opacities: &[(SurfaceId, f32)],
+        transforms: &[(SurfaceId, Transform)],
+        scroll_offsets: &[(SurfaceId, Vector2f)],
+    ) -> bool {
+        let bound = opacities.iter().all(|(s, _)| self.opacity_bindings.contains(s))
+            && transforms.iter().all(|(s, _)| self.transform_bindings.contains_key(s));
+
+        if !bound {
+            self.queue_scroll_offsets(scroll_offsets);
+            return false;
+        }
+
+        let mut tx = Transaction::new();
+
+        tx.update_dynamic_properties(DynamicProperties {
+            transforms: transforms
+                .iter()
+                .map(|(surface, transform)| PropertyValue {
+                    key: transform_key(*surface),
+                    value: layout_transform(transform, &self.transform_bindings[surface]),
+                })
+                .collect(),
+            floats: opacities
+                .iter()
+                .map(|(surface, opacity)| PropertyValue { key: opacity_key(*surface), value: *opacity })
+                .collect(),
+        });
+
+        for (surface, offset) in scroll_offsets {
+            scroll_node(&mut tx, *surface, offset.clone());
+        }
+
+        tx.generate_frame();
+
+        self.send_tx(tx);
+
+        true
+    }
+
     pub fn resize(&mut self, fb_size: (i32, i32), dpi: f32) {
         self.fb_size = FramebufferIntSize::new(fb_size.0, fb_size.1);
+        self.dpi = dpi;
         self.render_api.set_document_view(self.document_id, self.fb_size.into(), dpi);
     }
 }
@@ -162,10 +259,21 @@ impl SceneRenderer for WebrenderRenderer{
         let content_size = LayoutSize::new(width, height);
         let pipeline_id = PIPELINE_ID;
 
+        self.opacity_bindings.clear();
+        self.transform_bindings.clear();
+
         let builder = {
             let mut context = RenderContext {
                 scene,
                 render_api: &mut self.render_api,
+                dpi: self.dpi,
+                images: &mut self.images,
+                rasterized_images: &mut self.rasterized_images,
+                fonts: &mut self.fonts,
+                font_instances: &mut self.font_instances,
+                opacity_bindings: &mut self.opacity_bindings,
+                transform_bindings: &mut self.transform_bindings,
+                animated_opacities: &self.animated_opacities,
 
                 builder: DisplayListBuilder::with_capacity(
                     pipeline_id,
@@ -183,12 +291,24 @@ impl SceneRenderer for WebrenderRenderer{
         };
 
         self.send_frame(builder, content_size);
+
+        // removed/changed surfaces & old sizes
+        self.images.evict_unused(&self.render_api);
+        self.rasterized_images.evict_unused(&self.render_api);
     }
 }
 
 struct RenderContext<'a> {
     scene: &'a dyn Scene,
     render_api: &'a mut RenderApi,
+    dpi: f32,
+    images: &'a mut ImageCache<String>,
+    rasterized_images: &'a mut ImageCache<(u64, u32, u32)>,
+    fonts: &'a mut BTreeMap<FontFace, FontKey>,
+    font_instances: &'a mut BTreeMap<(FontFace, i32, Vec<(u32, u32)>), FontInstanceKey>,
+    opacity_bindings: &'a mut BTreeSet<SurfaceId>,
+    transform_bindings: &'a mut BTreeMap<SurfaceId, LayoutRect>,
+    animated_opacities: &'a BTreeSet<SurfaceId>,
 
     builder: DisplayListBuilder,
     border_radius: WRBorderRadius,
@@ -198,6 +318,14 @@ struct RenderContext<'a> {
 
 impl<'a> RenderContext<'a> {
     fn render_surface(&mut self, surface: SurfaceId) {
+        // display: none cuts the whole subtree (and it takes no space)
+        if let Display::None = self.scene.display(surface) {
+            return;
+        }
+
+        // invisible surfaces still take space and their children might be visible
+        let hidden = self.scene.is_hidden(surface);
+
         let parent_layout = self.layout;
         let parent_space_and_clip = self.space_and_clip;
 
@@ -208,14 +336,37 @@ impl<'a> RenderContext<'a> {
                 .translate(&parent_layout.rect.origin.to_vector()),
         );
 
-        // everything will receive events (important for onMouseLeave)
-        self.layout.tag = Some((0, surface as u16));
+        // everything (except hidden) will receive events (important for onMouseLeave)
+        if !hidden {
+            self.layout.tag = Some(hit_tag(surface, None));
+        }
 
         debug!("surface {} {:?}", surface, self.layout.rect);
 
+        // the whole subtree (including shadow) is transformed
+        let reference_frame = match self.scene.transform(surface) {
+            Some(transform) => {
+                self.push_reference_frame(surface, transform.clone());
+                true
+            }
+            None => false,
+        };
+
+        // the whole subtree (including shadow) should be composited (& faded) together
+        let opacity = opacity_binding(self.scene.opacity(surface), self.animated_opacities.contains(&surface));
+        let stacking_context = opacity.is_some() || self.scene.z_index(surface).is_some();
+
+        if stacking_context {
+            self.push_stacking_context(surface, opacity);
+        }
+
+        // shadow should be clipped by parent not by us (but it should be transformed)
+        let shadow_space_and_clip = self.space_and_clip;
+
         // shared, not directly rendered
+        // (own content is always clipped, children only if the overflow is not visible)
         if let Some(border_radius) = self.scene.border_radius(surface) {
-            self.border_radius = border_radius.clone().into();
+            self.border_radius = resolve_border_radius(border_radius, &self.layout.rect.size);
 
             let clip_region = ComplexClipRegion::new(self.layout.clip_rect.clone(), self.border_radius, ClipMode::Clip);
             let clip_id = self.builder.define_clip(&self.space_and_clip, self.layout.clip_rect, vec![clip_region], None);
@@ -226,50 +377,16 @@ impl<'a> RenderContext<'a> {
             self.border_radius = WRBorderRadius::zero();
         }
 
-        if let Some(box_shadow) = self.scene.box_shadow(surface) {
-            let Vector2f(x, y) = box_shadow.offset;
-            let size = box_shadow.spread + box_shadow.blur;
-            let layout = LayoutPrimitiveInfo::with_clip_rect(
-                self.layout.rect,
-                self.layout
-                    .rect
-                    .translate(&TypedVector2D::new(x, y))
-                    .inflate(size, size),
-            );
-            self.builder.push_item(
-                &self.box_shadow(box_shadow.clone()),
-                // TODO: (outset) shadow shouldn't have tag (& receive events)
-                &layout,
-                // note that we are using parent clip (shadow should be clipped by parent not by us)
-                &parent_space_and_clip,
-            );
-        }
-
-        if let Some(color) = self.scene.background_color(surface) {
-            self.push(self.background_color(color.clone()));
-        }
-
-        if let Some(image) = self.scene.image(surface) {
-            self.push(self.image(image.clone()));
+        if !hidden {
+            self.render_content(surface, shadow_space_and_clip);
         }
 
-        // TODO: selections (should be below text)
-        // (or it could be just overlay with inverse color "effect")
-
-        if let Some(text) = self.scene.text(surface) {
-            let (item, glyphs) = self.text(text.clone(), self.scene.text_layout(surface));
+        // children are not clipped by our border box
+        self.space_and_clip = shadow_space_and_clip;
 
-            // webrender has a limit on how long the text item can be
-            // TODO: use the const from webrender (couldn't find it quickly)
-            for glyphs in glyphs.chunks(2000) {
-                self.push(item.clone());
-                self.builder.push_iter(glyphs);
-            }
-        }
-
-        if let Some(border) = self.scene.border(surface) {
-            self.push(self.border(border.clone()));
-            // TODO: children should be in (possibly rounded) clip too so they can't overdraw border (or padding)
+        match self.scene.overflow(surface) {
+            Overflow::Visible => {}
+            _ => self.clip_padding_box(surface),
         }
 
         if let Some((width, height)) = self.scene.scroll_frame(surface) {
@@ -297,8 +414,28 @@ impl<'a> RenderContext<'a> {
         }
 
         // children has to be "on top" because of hitbox testing
-        for child_surface in self.scene.children(surface) {
-            self.render_surface(*child_surface);
+        // z-index only reorders siblings (there is no global stacking order like in CSS)
+        // absolutely positioned siblings go above the ones in the flow (with the same z-index)
+        let mut children = self.scene.children(surface).to_vec();
+        children.sort_by_key(|child| {
+            let absolute = match self.scene.position(*child) {
+                Some(Position { kind: PositionType::Absolute, .. }) => true,
+                _ => false,
+            };
+
+            (self.scene.z_index(*child).unwrap_or(0), absolute)
+        });
+
+        for child_surface in children {
+            self.render_surface(child_surface);
+        }
+
+        if stacking_context {
+            self.builder.pop_stacking_context();
+        }
+
+        if reference_frame {
+            self.builder.pop_reference_frame();
         }
 
         // restore layout
@@ -306,6 +443,151 @@ impl<'a> RenderContext<'a> {
         self.space_and_clip = parent_space_and_clip;
     }
 
+    // own content (everything except children)
+    fn render_content(&mut self, surface: SurfaceId, shadow_space_and_clip: SpaceAndClipInfo) {
+        // first shadow is on top (like in CSS) so they are pushed in reverse
+        let scene = self.scene;
+        let box_shadows = scene.box_shadows(surface);
+
+        for box_shadow in box_shadows.iter().rev().filter(|s| !s.inset) {
+            let Vector2f(x, y) = box_shadow.offset;
+            let size = box_shadow.spread + box_shadow.blur;
+            let mut layout = LayoutPrimitiveInfo::with_clip_rect(
+                self.layout.rect,
+                self.layout
+                    .rect
+                    .translate(&TypedVector2D::new(x, y))
+                    .inflate(size, size),
+            );
+
+            // it's outside so it shouldn't receive events
+            layout.tag = None;
+
+            let item = self.box_shadow(box_shadow.clone());
+            self.builder.push_item(&item, &layout, &shadow_space_and_clip);
+        }
+
+        if let Some(color) = self.scene.background_color(surface) {
+            self.push(self.background_color(color.clone()));
+        }
+
+        if let Some(background) = self.scene.background(surface) {
+            let item = self.background(background.clone());
+            self.push(item);
+        }
+
+        if let Some(image) = self.scene.image(surface) {
+            if let Some(item) = self.image(image) {
+                self.push(item);
+            }
+        }
+
+        // inset shadows are above the background (and inside of the rounded clip)
+        for box_shadow in box_shadows.iter().rev().filter(|s| s.inset) {
+            let item = self.box_shadow(box_shadow.clone());
+            self.push(item);
+        }
+
+        if let Some(svg) = self.scene.svg(surface) {
+            self.svg(svg);
+        }
+
+        if let (Some(text), Some(laid_text)) = (scene.text(surface), scene.text_layout(surface)) {
+            let text_input = scene.text_input(surface).filter(|i| i.is_focused());
+
+            // selection is below the text, caret is above
+            let (selection, caret) = match text_input {
+                Some(text_input) => match text_input.selection() {
+                    (start, end) if start != end => (laid_text.selection_rects(start, end), None),
+                    _ if text_input.caret_visible(Instant::now()) => (vec![], Some(laid_text.caret_rect(text_input.caret()))),
+                    _ => (vec![], None),
+                },
+                None => (vec![], None),
+            };
+            let text_width = laid_text.width;
+
+            for rect in selection {
+                self.text_rect(&text.align, text_width, rect, SELECTION_COLOR);
+            }
+
+            self.text(surface, text.clone(), laid_text);
+
+            if let Some(Rect(x, y, _, height)) = caret {
+                self.text_rect(&text.align, text_width, Rect(x, y, CARET_WIDTH, height), text.color.clone());
+            }
+        }
+
+        if let Some(border) = self.scene.border(surface) {
+            let item = self.border(border.clone());
+            self.push(item);
+        }
+    }
+
+    // so that children can't overdraw the (rounded) border nor anything outside
+    // (it's also respected by hit-testing so clipped-away children don't receive events)
+    fn clip_padding_box(&mut self, surface: SurfaceId) {
+        let (top, right, bottom, left) = match self.scene.border(surface) {
+            Some(b) => (b.top.width, b.right.width, b.bottom.width, b.left.width),
+            None => (0., 0., 0., 0.),
+        };
+
+        let rect = self.layout.rect.inner_rect(TypedSideOffsets2D::new(top, right, bottom, left));
+
+        // inner radii are smaller by the width of the border
+        let WRBorderRadius { top_left, top_right, bottom_left, bottom_right } = self.border_radius;
+        let inner = |radius: LayoutSize, x: f32, y: f32| LayoutSize::new((radius.width - x).max(0.), (radius.height - y).max(0.));
+        let radius = WRBorderRadius {
+            top_left: inner(top_left, left, top),
+            top_right: inner(top_right, right, top),
+            bottom_left: inner(bottom_left, left, bottom),
+            bottom_right: inner(bottom_right, right, bottom),
+        };
+
+        let clip_regions = match self.scene.border_radius(surface) {
+            Some(_) => vec![ComplexClipRegion::new(rect, radius, ClipMode::Clip)],
+            None => vec![],
+        };
+        let clip_id = self.builder.define_clip(&self.space_and_clip, rect, clip_regions, None);
+
+        self.space_and_clip.clip_id = clip_id;
+    }
+
+    fn push_reference_frame(&mut self, surface: SurfaceId, transform: Transform) {
+        let transform = layout_transform(&transform, &self.layout.rect);
+        self.transform_bindings.insert(surface, self.layout.rect);
+
+        self.space_and_clip.spatial_id = self.builder.push_reference_frame(
+            // zero origin because we are using absolute coordinates everywhere
+            &LayoutRect::zero(),
+            self.space_and_clip.spatial_id,
+            TransformStyle::Flat,
+            // binding so that it can be changed without rebuilding the display list
+            PropertyBinding::Binding(transform_key(surface), transform),
+            ReferenceFrameKind::Transform,
+        );
+    }
+
+    fn push_stacking_context(&mut self, surface: SurfaceId, opacity: Option<f32>) {
+        let mut filters = vec![];
+
+        // binding so that it can be changed without rebuilding the display list
+        if let Some(opacity) = opacity {
+            self.opacity_bindings.insert(surface);
+            filters.push(FilterOp::Opacity(PropertyBinding::Binding(opacity_key(surface), opacity), opacity));
+        }
+
+        self.builder.push_stacking_context(
+            // zero origin because we are using absolute coordinates everywhere
+            &LayoutPrimitiveInfo::new(LayoutRect::zero()),
+            self.space_and_clip.spatial_id,
+            None,
+            TransformStyle::Flat,
+            MixBlendMode::Normal,
+            &filters,
+            RasterSpace::Screen,
+        );
+    }
+
     fn box_shadow(&self, box_shadow: BoxShadow) -> SpecificDisplayItem {
         SpecificDisplayItem::BoxShadow(BoxShadowDisplayItem {
             color: box_shadow.color.clone().into(),
@@ -314,10 +596,10 @@ impl<'a> RenderContext<'a> {
             offset: box_shadow.offset.into(),
             blur_radius: box_shadow.blur,
             spread_radius: box_shadow.spread,
-            border_radius: self.border_radius.clone().into(),
+            border_radius: self.border_radius,
 
-            // TODO: Inset/Outset (outset needs bigger clip-rect)
-            clip_mode: BoxShadowClipMode::Outset,
+            // outset needs bigger clip-rect (see render_surface)
+            clip_mode: if box_shadow.inset { BoxShadowClipMode::Inset } else { BoxShadowClipMode::Outset },
         })
     }
 
@@ -328,93 +610,386 @@ impl<'a> RenderContext<'a> {
     }
 
     // TODO: refactor, cache, free + hook to make loading possible from node.js (http)
-    fn image(&self, image: Image) -> SpecificDisplayItem {
-        let image_key = {
-            let mut f = File::open(image.url).expect("couldn't open file");
-            let mut buffer = Vec::new();
-            f.read_to_end(&mut buffer).expect("couldn't read");
-
-            let image = image::load_from_memory(&buffer).expect("couldn't load image");
-            let descriptor = ImageDescriptor::new(
-                image.width() as i32,
-                image.height() as i32,
-                ImageFormat::RGBA8,
-                true,
-                false,
-            );
+    // nothing is drawn if the image can't be loaded
+    fn image(&mut self, image: &Image) -> Option<SpecificDisplayItem> {
+        let (image_key, _) = self.upload_image(&image.url)?;
 
-            let key = self.render_api.generate_image_key();
-
-            self.render_api
-                .update_resources(vec![ResourceUpdate::AddImage(AddImage {
-                    key,
-                    descriptor,
-                    data: ImageData::new(
-                        image
-                            .as_rgba8()
-                            .expect("couldn't convert to rgba8")
-                            .to_vec(),
-                    ),
-                    tiling: None,
-                })]);
-
-            key
-        };
-
-        SpecificDisplayItem::Image(ImageDisplayItem {
+        Some(SpecificDisplayItem::Image(ImageDisplayItem {
             image_key,
             stretch_size: self.layout.rect.size.into(),
             tile_spacing: TypedSize2D::zero(),
             image_rendering: ImageRendering::Auto,
             alpha_type: AlphaType::PremultipliedAlpha,
             color: ColorF::WHITE,
-        })
+        }))
     }
 
-    // TODO: clip should be enough big to contain `y` and similar characters
-    fn text(&self, text: Text, laid_text: LaidText) -> (SpecificDisplayItem, Vec<GlyphInstance>) {
-        let [mut text_x, text_y] = self.layout.rect.origin.to_array();
-        // TODO: text-right
+    // returns key & (original) size
+    // uploaded only once (and freed when it's not used anymore)
+    fn upload_image(&mut self, url: &str) -> Option<(ImageKey, (u32, u32))> {
+        self.images.get_or_upload(self.render_api, url.to_string(), true, || load_image(url))
+    }
+
+    // gradient points are relative to the surface (unlike everything else)
+    fn background(&mut self, background: Background) -> SpecificDisplayItem {
+        let size = self.layout.rect.size;
 
-        if let TextAlign::Center = text.align {
-            text_x = text_x + (self.layout.rect.size.width - laid_text.width) / 2.;
+        match background {
+            Background::LinearGradient(gradient) => SpecificDisplayItem::Gradient(GradientDisplayItem {
+                gradient: self.linear_gradient(&gradient, size),
+                tile_size: size,
+                tile_spacing: LayoutSize::zero(),
+            }),
+            Background::RadialGradient(gradient) => SpecificDisplayItem::RadialGradient(RadialGradientDisplayItem {
+                gradient: self.radial_gradient(&gradient, size),
+                tile_size: size,
+                tile_spacing: LayoutSize::zero(),
+            }),
+            // not supported by webrender (yet)
+            Background::ConicGradient(gradient) => {
+                let key = hash_key(&format!("{:?}", &gradient));
+
+                match self.rasterized(key, |width, height| Some(rasterize_conic_gradient(&gradient, width, height))) {
+                    Some((image_key, _)) => self.raster_image(image_key, size),
+                    None => self.background_color(Color(0, 0, 0, 0)),
+                }
+            }
         }
+    }
 
-        let glyphs = laid_text.glyphs
-            .iter()
-            .map(|LaidGlyph { glyph_index, x, y }| GlyphInstance {
+    // note that this also pushes the stops (so it has to be followed by the item)
+    fn linear_gradient(&mut self, gradient: &LinearGradient, size: LayoutSize) -> Gradient {
+        let (start, end) = linear_gradient_line(gradient.angle, size);
+
+        self.builder.create_gradient(start, end, gradient_stops(&gradient.stops), ExtendMode::Clamp)
+    }
+
+    fn radial_gradient(&mut self, gradient: &RadialGradient, size: LayoutSize) -> WRRadialGradient {
+        let RadialGradient { center, radius, stops } = gradient;
+
+        self.builder.create_radial_gradient(
+            LayoutPoint::new(center.0 * size.width, center.1 * size.height),
+            LayoutSize::new(radius.0 * size.width, radius.1 * size.height),
+            gradient_stops(stops),
+            ExtendMode::Clamp,
+        )
+    }
+
+    // the aspect ratio is kept and the image is centered (invalid or empty svgs are skipped)
+    fn svg(&mut self, svg: &Svg) {
+        let rect = self.layout.rect;
+
+        if let Some((image_key, size)) = self.rasterized(svg::cache_key(svg), |width, height| svg::rasterize(svg, width, height)) {
+            let origin = rect.origin + LayoutVector2D::new((rect.size.width - size.width) / 2., (rect.size.height - size.height) / 2.);
+            let mut layout = LayoutPrimitiveInfo::with_clip_rect(LayoutRect::new(origin, size), self.layout.clip_rect);
+            layout.tag = None;
+
+            self.builder.push_item(&self.raster_image(image_key, size), &layout, &self.space_and_clip);
+        }
+
+        // the image can be smaller so the whole surface is hit instead (relative to the surface)
+        self.push(self.background_color(Color(0, 0, 0, 0)));
+    }
+
+    // rasterized for the current dpi so it's always sharp (and then cached for the given size)
+    // returns the key & the size of the result (in layout units)
+    fn rasterized(&mut self, key: u64, rasterize: impl FnOnce(u32, u32) -> Option<RgbaImage>) -> Option<(ImageKey, LayoutSize)> {
+        let size = self.layout.rect.size;
+        let width = (size.width * self.dpi).ceil() as u32;
+        let height = (size.height * self.dpi).ceil() as u32;
+
+        if (width == 0) || (height == 0) {
+            return None;
+        }
+
+        let (image_key, (width, height)) = self.rasterized_images.get_or_upload(self.render_api, (key, width, height), false, || rasterize(width, height))?;
+
+        Some((image_key, LayoutSize::new(width as f32 / self.dpi, height as f32 / self.dpi)))
+    }
+
+    fn raster_image(&self, image_key: ImageKey, size: LayoutSize) -> SpecificDisplayItem {
+        SpecificDisplayItem::Image(ImageDisplayItem {
+            image_key,
+            stretch_size: size,
+            tile_spacing: TypedSize2D::zero(),
+            image_rendering: ImageRendering::Auto,
+            // neither nanosvg nor our gradients are premultiplied
+            alpha_type: AlphaType::Alpha,
+            color: ColorF::WHITE,
+        })
+    }
+
+    // TODO: clip should be enough big to contain `y` and similar characters
+    // every span (or fallback font) can have different font & color so there might be more text items
+    // decorations are pushed after the text (so they are shadowed too) and then link "hitboxes"
+    fn text(&mut self, surface: SurfaceId, text: Text, laid_text: LaidText) {
+        let [text_x, text_y] = self.layout.rect.origin.to_array();
+
+        // lines are already aligned inside of the text block but the rect might be wider
+        let text_x = text_x + align_offset(&text.align, self.layout.rect.size.width, laid_text.width);
+
+        let metrics = &laid_text.metrics;
+        let mut runs: Vec<((FontInstanceKey, ColorF), Vec<GlyphInstance>)> = vec![];
+
+        // once per font (glyphs of fonts which couldn't be loaded are skipped)
+        let font_keys: Vec<Option<FontInstanceKey>> =
+            laid_text.fonts.iter().map(|font| self.font_instance(font, &text.font_variations)).collect();
+
+        for LaidGlyph { glyph_index, font, x, y, span, .. } in &laid_text.glyphs {
+            let span = span.map(|i| &text.spans[i]);
+            let font_key = match font_keys[*font] {
+                Some(font_key) => font_key,
+                None => continue,
+            };
+            let color: ColorF = span.and_then(|s| s.color.clone()).unwrap_or(text.color.clone()).into();
+            let glyph = GlyphInstance {
                 index: *glyph_index,
                 point: LayoutPoint::new(text_x + x, text_y + y),
-            })
-            .collect();
+            };
 
-        let font_key = FontInstanceKey::new(self.render_api.get_namespace_id(), text.font_size as u32);
+            match runs.last_mut() {
+                Some((run_key, glyphs)) if *run_key == (font_key, color) => glyphs.push(glyph),
+                _ => runs.push(((font_key, color), vec![glyph])),
+            }
+        }
 
-        let item = SpecificDisplayItem::Text(TextDisplayItem {
-            font_key,
-            color: text.color.clone().into(),
-            glyph_options: None,
+        // everything until pop is shadowed, first shadow should be on top
+        for TextShadow { offset, blur, color } in text.shadows.iter().rev() {
+            self.builder.push_shadow(
+                &self.layout,
+                &self.space_and_clip,
+                Shadow {
+                    offset: LayoutVector2D::new(offset.0, offset.1),
+                    color: color.clone().into(),
+                    blur_radius: *blur,
+                },
+            );
+        }
+
+        for ((font_key, color), glyphs) in runs {
+            let item = SpecificDisplayItem::Text(TextDisplayItem {
+                font_key,
+                color,
+                glyph_options: None,
+            });
+
+            // webrender has a limit on how long the text item can be
+            // TODO: use the const from webrender (couldn't find it quickly)
+            for glyphs in glyphs.chunks(2000) {
+                self.push(item.clone());
+                self.builder.push_iter(glyphs);
+            }
+        }
+
+        for (span, Rect(x, y, width, _)) in &laid_text.span_rects {
+            let span = &text.spans[*span];
+
+            if span.underline {
+                let baseline = LayoutPoint::new(text_x + x, text_y + y + laid_text.baseline);
+                let color = span.color.clone().unwrap_or(text.color.clone());
+
+                self.decoration_line(
+                    baseline + LayoutVector2D::new(0., metrics.underline_offset),
+                    *width,
+                    metrics.underline_thickness,
+                    &TextDecorationStyle::Solid,
+                    color,
+                );
+            }
+        }
+
+        if let Some(decoration) = &text.decoration {
+            let color = decoration.color.clone().unwrap_or(text.color.clone());
+
+            for Rect(x, y, width, _) in &laid_text.line_rects {
+                let baseline = LayoutPoint::new(text_x + x, text_y + y + laid_text.baseline);
+                let mut lines = vec![];
+
+                if decoration.underline {
+                    lines.push((metrics.underline_offset, metrics.underline_thickness));
+                }
+
+                if decoration.overline {
+                    lines.push((-metrics.ascent, metrics.underline_thickness));
+                }
+
+                if decoration.line_through {
+                    lines.push((metrics.strikethrough_offset, metrics.strikethrough_thickness));
+                }
+
+                for (offset, thickness) in lines {
+                    let origin = baseline + LayoutVector2D::new(0., offset);
+
+                    self.decoration_line(origin, *width, thickness, &decoration.style, color.clone());
+                }
+            }
+        }
+
+        if !text.shadows.is_empty() {
+            self.builder.pop_all_shadows();
+        }
+
+        for (span, Rect(x, y, width, height)) in laid_text.span_rects {
+            let span = &text.spans[span];
+            let origin = LayoutPoint::new(text_x + x, text_y + y);
+
+            // the rect is the whole surface (clipped to the span) so that the hit point is relative to the surface
+            if let Some(link) = span.link {
+                let span_rect = LayoutRect::new(origin, LayoutSize::new(width, height));
+                let clip_rect = span_rect.intersection(&self.layout.clip_rect).unwrap_or(LayoutRect::zero());
+                let mut layout = LayoutPrimitiveInfo::with_clip_rect(self.layout.rect, clip_rect);
+                layout.tag = Some(hit_tag(surface, Some(link)));
+
+                self.builder.push_item(&self.background_color(Color(0, 0, 0, 0)), &layout, &self.space_and_clip);
+            }
+        }
+    }
+
+    // selections & carets, they are not hit-testable so that the surface gets the point relative to itself
+    fn text_rect(&mut self, align: &TextAlign, text_width: f32, Rect(x, y, width, height): Rect, color: Color) {
+        let [text_x, text_y] = self.layout.rect.origin.to_array();
+        let text_x = text_x + align_offset(align, self.layout.rect.size.width, text_width);
+
+        let mut layout = LayoutPrimitiveInfo::new(LayoutRect::new(
+            LayoutPoint::new(text_x + x, text_y + y),
+            LayoutSize::new(width, height),
+        ));
+        layout.tag = None;
+
+        self.builder.push_item(&self.background_color(color), &layout, &self.space_and_clip);
+    }
+
+    fn decoration_line(&mut self, origin: LayoutPoint, width: f32, thickness: f32, style: &TextDecorationStyle, color: Color) {
+        let (style, height) = match style {
+            TextDecorationStyle::Solid => (LineStyle::Solid, thickness),
+            TextDecorationStyle::Dotted => (LineStyle::Dotted, thickness),
+            TextDecorationStyle::Dashed => (LineStyle::Dashed, thickness),
+            // the rect has to be big enough for the wave
+            TextDecorationStyle::Wavy => (LineStyle::Wavy, thickness * 3.),
+            // webrender has no double line
+            TextDecorationStyle::Double => {
+                self.decoration_line(origin, width, thickness, &TextDecorationStyle::Solid, color.clone());
+
+                return self.decoration_line(
+                    origin + LayoutVector2D::new(0., thickness * 2.),
+                    width,
+                    thickness,
+                    &TextDecorationStyle::Solid,
+                    color,
+                );
+            }
+        };
+
+        // not hit-testable (like text rects), the surface is hit instead
+        let mut layout = LayoutPrimitiveInfo::new(LayoutRect::new(origin, LayoutSize::new(width, height)));
+        layout.tag = None;
+
+        let item = SpecificDisplayItem::Line(LineDisplayItem {
+            orientation: LineOrientation::Horizontal,
+            wavy_line_thickness: thickness,
+            color: color.into(),
+            style,
         });
 
-        (item, glyphs)
+        self.builder.push_item(&item, &layout, &self.space_and_clip);
     }
 
-    fn border(&self, border: Border) -> SpecificDisplayItem {
-        SpecificDisplayItem::Border(BorderDisplayItem {
-            widths: TypedSideOffsets2D::new(
-                border.top.width,
-                border.right.width,
-                border.bottom.width,
-                border.left.width,
-            ),
-            details: BorderDetails::Normal(NormalBorder {
+    // created lazily, on the first use
+    //
+    // features are only used for shaping (in the layout) but variations affect also the rasterization
+    fn font_instance(&mut self, font: &LaidFont, variations: &[FontVariation]) -> Option<FontInstanceKey> {
+        let render_api = &*self.render_api;
+        let face = (font.family.clone(), font.bold, font.italic, font.file.clone());
+        let glyph_size = app_units::Au::from_f32_px(font.size);
+        let variations: Vec<(u32, u32)> = variations.iter().map(|v| (font_tag(&v.tag), v.value.to_bits())).collect();
+
+        if let Some(key) = self.font_instances.get(&(face.clone(), glyph_size.0, variations.clone())) {
+            return Some(*key);
+        }
+
+        let font_key = match self.fonts.get(&face) {
+            Some(font_key) => *font_key,
+            None => {
+                let font_key = load_font(render_api, font)?;
+                self.fonts.insert(face.clone(), font_key);
+                font_key
+            }
+        };
+        let key = render_api.generate_font_instance_key();
+
+        render_api.update_resources(vec![ResourceUpdate::AddFontInstance(AddFontInstance {
+            key,
+            font_key,
+            glyph_size,
+            options: None,
+            platform_options: None,
+            variations: variations
+                .iter()
+                .map(|(tag, value)| WRFontVariation { tag: *tag, value: f32::from_bits(*value) })
+                .collect(),
+        })]);
+
+        self.font_instances.insert((face, glyph_size.0, variations), key);
+
+        Some(key)
+    }
+
+    fn border(&mut self, border: Border) -> SpecificDisplayItem {
+        let widths = TypedSideOffsets2D::new(
+            border.top.width,
+            border.right.width,
+            border.bottom.width,
+            border.left.width,
+        );
+
+        let details = match border.image.and_then(|image| self.nine_patch(image)) {
+            Some(nine_patch) => BorderDetails::NinePatch(nine_patch),
+            None => BorderDetails::Normal(NormalBorder {
                 top: border.top.into(),
                 right: border.right.into(),
                 bottom: border.bottom.into(),
                 left: border.left.into(),
-                radius: self.border_radius.clone().into(),
+                radius: self.border_radius,
                 do_aa: true,
             }),
+        };
+
+        SpecificDisplayItem::Border(BorderDisplayItem { widths, details })
+    }
+
+    // like css border-image, slices are in pixels of the source
+    // (normal border is used if the image can't be loaded)
+    fn nine_patch(&mut self, image: BorderImage) -> Option<NinePatchBorder> {
+        let size = self.layout.rect.size;
+        let BorderImageSlice(top, right, bottom, left) = image.slice;
+        let repeat = image.repeat.into();
+
+        let (source, (width, height)) = match image.source {
+            BorderImageSource::Image(image) => {
+                let (key, size) = self.upload_image(&image.url)?;
+
+                (NinePatchBorderSource::Image(key), size)
+            }
+            // gradients are as big as the surface
+            BorderImageSource::LinearGradient(gradient) => (
+                NinePatchBorderSource::Gradient(self.linear_gradient(&gradient, size)),
+                (size.width as u32, size.height as u32),
+            ),
+            BorderImageSource::RadialGradient(gradient) => (
+                NinePatchBorderSource::RadialGradient(self.radial_gradient(&gradient, size)),
+                (size.width as u32, size.height as u32),
+            ),
+        };
+
+        Some(NinePatchBorder {
+            source,
+            width: width as i32,
+            height: height as i32,
+            slice: SideOffsets2D::new(top as i32, right as i32, bottom as i32, left as i32),
+            fill: image.fill,
+            repeat_horizontal: repeat,
+            repeat_vertical: repeat,
+            outset: SideOffsets2D::zero(),
         })
     }
 
@@ -426,14 +1001,257 @@ impl<'a> RenderContext<'a> {
     }
 }
 
+/// Uploaded images which are freed when they were not used in the last render
+/// (surface was removed, its image was changed or it has a different size now)
+struct ImageCache<K: Ord> {
+    // key & size (in pixels)
+    images: BTreeMap<K, (ImageKey, (u32, u32))>,
+    used: BTreeSet<K>,
+}
+
+impl<K: Ord + Clone> ImageCache<K> {
+    fn new() -> Self {
+        ImageCache { images: BTreeMap::new(), used: BTreeSet::new() }
+    }
+
+    // nothing is uploaded (and cached) if there's no image (or if it's empty)
+    fn get_or_upload(&mut self, render_api: &RenderApi, key: K, is_opaque: bool, load: impl FnOnce() -> Option<RgbaImage>) -> Option<(ImageKey, (u32, u32))> {
+        if !self.images.contains_key(&key) {
+            let image = load().filter(|i| (i.width() > 0) && (i.height() > 0))?;
+            let size = (image.width(), image.height());
+            let descriptor = ImageDescriptor::new(size.0 as i32, size.1 as i32, ImageFormat::RGBA8, is_opaque, false);
+            let image_key = render_api.generate_image_key();
+
+            render_api.update_resources(vec![ResourceUpdate::AddImage(AddImage {
+                key: image_key,
+                descriptor,
+                data: ImageData::new(image.into_raw()),
+                tiling: None,
+            })]);
+
+            self.images.insert(key.clone(), (image_key, size));
+        }
+
+        self.used.insert(key.clone());
+
+        self.images.get(&key).cloned()
+    }
+
+    fn evict_unused(&mut self, render_api: &RenderApi) {
+        let used = std::mem::replace(&mut self.used, BTreeSet::new());
+        let unused: Vec<K> = self.images.keys().filter(|k| !used.contains(k)).cloned().collect();
+
+        if unused.is_empty() {
+            return;
+        }
+
+        let updates = unused
+            .iter()
+            .filter_map(|k| self.images.remove(k))
+            .map(|(image_key, _)| ResourceUpdate::DeleteImage(image_key))
+            .collect();
+
+        render_api.update_resources(updates);
+    }
+}
+
+fn scroll_node(tx: &mut Transaction, surface: SurfaceId, Vector2f(x, y): Vector2f) {
+    tx.scroll_node_with_id(LayoutPoint::new(x, y), ExternalScrollId(surface as u64, PIPELINE_ID), ScrollClamping::ToContentBounds);
+}
+
+fn load_image(url: &str) -> Option<RgbaImage> {
+    let mut buffer = Vec::new();
+
+    if let Err(err) = File::open(url).and_then(|mut f| f.read_to_end(&mut buffer)) {
+        error!("couldn't read image {} {:?}", url, err);
+        return None;
+    }
+
+    match image::load_from_memory(&buffer) {
+        Ok(image) => Some(image.to_rgba()),
+        Err(err) => {
+            error!("couldn't load image {} {:?}", url, err);
+            None
+        }
+    }
+}
+
+// what should be bound (if anything), animated surfaces are bound even if they are opaque
+fn opacity_binding(opacity: Option<f32>, animated: bool) -> Option<f32> {
+    match (opacity, animated) {
+        (Some(opacity), _) => Some(opacity),
+        (None, true) => Some(1.),
+        (None, false) => None,
+    }
+}
+
+fn hash_key(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+
+    hasher.finish()
+}
+
 // unlike browser, we are going to have only one pipeline (per window)
 static PIPELINE_ID: PipelineId = PipelineId(0, 0);
 
 static BUILDER_CAPACITY: usize = 512 * 1024;
 
+// like the default in macos
+static SELECTION_COLOR: Color = Color(179, 215, 255, 255);
+
+static CARET_WIDTH: f32 = 1.;
+
 // no idea but it's very slow otherwise
 static SCROLL_FACTOR: f32 = 5.0;
 
+fn opacity_key(surface: SurfaceId) -> PropertyBindingKey<f32> {
+    PropertyBindingKey::new(surface as u64)
+}
+
+fn transform_key(surface: SurfaceId) -> PropertyBindingKey<LayoutTransform> {
+    PropertyBindingKey::new(surface as u64)
+}
+
+// link id is in the first part of the tag (shifted because 0 means no link)
+fn hit_tag(surface: SurfaceId, link: Option<u32>) -> ItemTag {
+    (link.map_or(0, |link| link as u64 + 1), surface as u16)
+}
+
+fn hit_target((link, surface): ItemTag) -> (SurfaceId, Option<u32>) {
+    (surface as usize, link.checked_sub(1).map(|link| link as u32))
+}
+
+// opentype tags are 4 ascii chars packed to u32, ie. "wght"
+fn font_tag(tag: &str) -> u32 {
+    tag.bytes().take(4).fold(0, |res, b| (res << 8) | (b as u32))
+}
+
+// the same font has to be used by pango, otherwise glyph indices would not match
+// (so it's the same file if we know it, otherwise we have to find it by the family)
+fn load_font(render_api: &RenderApi, font: &LaidFont) -> Option<FontKey> {
+    let (bytes, index) = match &font.file {
+        Some((path, index)) => match std::fs::read(path) {
+            Ok(bytes) => (bytes, *index),
+            Err(err) => {
+                error!("couldn't read font {} {:?}", path, err);
+                return None;
+            }
+        },
+        None => {
+            let mut property = font_loader::system_fonts::FontPropertyBuilder::new().family(&font.family);
+
+            if font.bold {
+                property = property.bold();
+            }
+
+            if font.italic {
+                property = property.italic();
+            }
+
+            match font_loader::system_fonts::get(&property.build()) {
+                Some((bytes, index)) => (bytes, index as u32),
+                None => {
+                    error!("couldn't find font {:?}", font);
+                    return None;
+                }
+            }
+        }
+    };
+    let key = render_api.generate_font_key();
+
+    render_api.update_resources(vec![ResourceUpdate::AddFont(AddFont::Raw(key, bytes, index))]);
+
+    Some(key)
+}
+
+// family, bold, italic & file (with face index)
+type FontFace = (String, bool, bool, Option<(String, u32)>);
+
+// transform around the origin, in absolute coordinates (like everything else)
+fn layout_transform(transform: &Transform, rect: &LayoutRect) -> LayoutTransform {
+    let TransformOrigin(x, y) = &transform.origin;
+    let origin = rect.origin + LayoutVector2D::new(resolve(x, rect.size.width), resolve(y, rect.size.height));
+
+    // the last op is applied first (like in CSS)
+    transform.ops
+        .iter()
+        .fold(LayoutTransform::create_translation(origin.x, origin.y, 0.), |res, op| res.pre_mul(&op_transform(op)))
+        .pre_mul(&LayoutTransform::create_translation(-origin.x, -origin.y, 0.))
+}
+
+fn op_transform(op: &TransformOp) -> LayoutTransform {
+    match op {
+        TransformOp::Matrix(Matrix(a, b, c, d, e, f)) => LayoutTransform::row_major_2d(*a, *b, *c, *d, *e, *f),
+        TransformOp::Translate(Vector2f(x, y)) => LayoutTransform::create_translation(*x, *y, 0.),
+        TransformOp::Scale(Vector2f(x, y)) => LayoutTransform::create_scale(*x, *y, 1.),
+        TransformOp::Rotate(deg) => LayoutTransform::create_rotation(0., 0., 1., Angle::radians(deg.to_radians())),
+        TransformOp::Skew(Vector2f(x, y)) => LayoutTransform::create_skew(Angle::radians(x.to_radians()), Angle::radians(y.to_radians())),
+    }
+}
+
+// resolve (possibly relative) dimension against the given size
+fn resolve(dimension: &Dimension, size: f32) -> f32 {
+    match dimension {
+        Dimension::Auto => 0.,
+        Dimension::Point(v) => *v,
+        Dimension::Percent(p) => size * p / 100.,
+    }
+}
+
+// like in CSS, the line goes through the center and it's long enough so that
+// the corners get exactly the first/last color
+fn linear_gradient_line(angle: f32, size: LayoutSize) -> (LayoutPoint, LayoutPoint) {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let length = (size.width * sin).abs() + (size.height * cos).abs();
+    let center = LayoutPoint::new(size.width / 2., size.height / 2.);
+    let delta = LayoutVector2D::new(sin, -cos) * (length / 2.);
+
+    (center - delta, center + delta)
+}
+
+fn gradient_stops(stops: &[ColorStop]) -> Vec<GradientStop> {
+    stops
+        .iter()
+        .map(|ColorStop { offset, color }| GradientStop {
+            offset: *offset,
+            color: color.clone().into(),
+        })
+        .collect()
+}
+
+fn rasterize_conic_gradient(gradient: &ConicGradient, width: u32, height: u32) -> RgbaImage {
+    let Vector2f(cx, cy) = gradient.center;
+    let (cx, cy) = (cx * width as f32, cy * height as f32);
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        // clockwise, starting at the top (like in CSS)
+        let deg = (x as f32 + 0.5 - cx).atan2(cy - (y as f32 + 0.5)).to_degrees();
+        let t = (deg - gradient.angle) / 360.;
+        let Color(r, g, b, a) = sample_stops(&gradient.stops, t - t.floor());
+
+        image::Rgba([r, g, b, a])
+    })
+}
+
+// hard stops (same offsets) switch immediately
+fn sample_stops(stops: &[ColorStop], t: f32) -> Color {
+    match stops.iter().position(|stop| stop.offset >= t) {
+        None => stops.last().map(|stop| stop.color.clone()).unwrap_or(Color(0, 0, 0, 0)),
+        Some(0) => stops[0].color.clone(),
+        Some(i) => {
+            let (a, b) = (&stops[i - 1], &stops[i]);
+            let t = match b.offset > a.offset {
+                true => (t - a.offset) / (b.offset - a.offset),
+                false => 1.,
+            };
+            let c = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+
+            Color(c(a.color.0, b.color.0), c(a.color.1, b.color.1), c(a.color.2, b.color.2), c(a.color.3, b.color.3))
+        }
+    }
+}
+
 impl Into<ColorF> for Color {
     fn into(self) -> ColorF {
         let Color(r, g, b, a) = self;
@@ -447,14 +1265,27 @@ impl Into<LayoutVector2D> for Vector2f {
     }
 }
 
-impl Into<WRBorderRadius> for BorderRadius {
-    fn into(self) -> WRBorderRadius {
-        WRBorderRadius {
-            top_left: LayoutSize::new(self.0, self.0),
-            top_right: LayoutSize::new(self.1, self.1),
-            bottom_left: LayoutSize::new(self.2, self.2),
-            bottom_right: LayoutSize::new(self.3, self.3),
-        }
+// percents are relative to the size, overlapping radii are scaled down (like in CSS)
+fn resolve_border_radius(border_radius: &BorderRadius, size: &LayoutSize) -> WRBorderRadius {
+    let corner = |CornerRadius(x, y): &CornerRadius| LayoutSize::new(resolve(x, size.width), resolve(y, size.height));
+    let BorderRadius(top_left, top_right, bottom_left, bottom_right) = border_radius;
+    let (top_left, top_right) = (corner(top_left), corner(top_right));
+    let (bottom_left, bottom_right) = (corner(bottom_left), corner(bottom_right));
+
+    let factor = [
+        size.width / (top_left.width + top_right.width),
+        size.width / (bottom_left.width + bottom_right.width),
+        size.height / (top_left.height + bottom_left.height),
+        size.height / (top_right.height + bottom_right.height),
+    ]
+    .iter()
+    .fold(1f32, |res, f| res.min(*f));
+
+    WRBorderRadius {
+        top_left: top_left * factor,
+        top_right: top_right * factor,
+        bottom_left: bottom_left * factor,
+        bottom_right: bottom_right * factor,
     }
 }
 
@@ -467,12 +1298,29 @@ impl Into<WRBorderSide> for BorderSide {
     }
 }
 
-// TODO: more styles
 impl Into<WRBorderStyle> for BorderStyle {
     fn into(self) -> WRBorderStyle {
         match self {
             BorderStyle::None => WRBorderStyle::None,
             BorderStyle::Solid => WRBorderStyle::Solid,
+            BorderStyle::Dashed => WRBorderStyle::Dashed,
+            BorderStyle::Dotted => WRBorderStyle::Dotted,
+            BorderStyle::Double => WRBorderStyle::Double,
+            BorderStyle::Groove => WRBorderStyle::Groove,
+            BorderStyle::Ridge => WRBorderStyle::Ridge,
+            BorderStyle::Inset => WRBorderStyle::Inset,
+            BorderStyle::Outset => WRBorderStyle::Outset,
+        }
+    }
+}
+
+impl Into<RepeatMode> for BorderImageRepeat {
+    fn into(self) -> RepeatMode {
+        match self {
+            BorderImageRepeat::Stretch => RepeatMode::Stretch,
+            BorderImageRepeat::Repeat => RepeatMode::Repeat,
+            BorderImageRepeat::Round => RepeatMode::Round,
+            BorderImageRepeat::Space => RepeatMode::Space,
         }
     }
 }
@@ -499,6 +1347,200 @@ impl RenderNotifier for SyncNotifier {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Dimensions, Size};
+    use crate::scene::ArrayScene;
+
+    #[test]
+    fn test_opacity_binding() {
+        assert_eq!(opacity_binding(None, false), None);
+        assert_eq!(opacity_binding(Some(0.5), false), Some(0.5));
+
+        // animation starting at (or going through) 1
+        assert_eq!(opacity_binding(None, true), Some(1.));
+        assert_eq!(opacity_binding(Some(0.5), true), Some(0.5));
+    }
+
+    fn transform(ops: Vec<TransformOp>, origin: (Dimension, Dimension)) -> Transform {
+        Transform { ops, origin: TransformOrigin(origin.0, origin.1) }
+    }
+
+    fn map(transform: &Transform, rect: &LayoutRect, (x, y): (f32, f32)) -> (f32, f32) {
+        let point = layout_transform(transform, rect).transform_point2d(&LayoutPoint::new(x, y)).unwrap();
+
+        ((point.x * 1000.).round() / 1000., (point.y * 1000.).round() / 1000.)
+    }
+
+    #[test]
+    fn test_layout_transform_origin() {
+        let rect = LayoutRect::new(LayoutPoint::new(10., 10.), LayoutSize::new(100., 50.));
+        let scale = vec![TransformOp::Scale(Vector2f(2., 2.))];
+
+        // origin is relative to the rect (top-left corner stays)
+        let t = transform(scale.clone(), (Dimension::Point(0.), Dimension::Point(0.)));
+        assert_eq!(map(&t, &rect, (10., 10.)), (10., 10.));
+        assert_eq!(map(&t, &rect, (20., 20.)), (30., 30.));
+
+        // center stays
+        let t = transform(scale.clone(), (Dimension::Percent(50.), Dimension::Percent(50.)));
+        assert_eq!(map(&t, &rect, (60., 35.)), (60., 35.));
+        assert_eq!(map(&t, &rect, (10., 10.)), (-40., -15.));
+
+        let t = transform(scale, (Dimension::Point(100.), Dimension::Percent(100.)));
+        assert_eq!(map(&t, &rect, (110., 60.)), (110., 60.));
+    }
+
+    #[test]
+    fn test_layout_transform_matrix() {
+        let rect = LayoutRect::new(LayoutPoint::zero(), LayoutSize::new(100., 100.));
+        let origin = (Dimension::Point(0.), Dimension::Point(0.));
+
+        // CSS matrix(a, b, c, d, e, f) maps (x, y) to (ax + cy + e, bx + dy + f)
+        let t = transform(vec![TransformOp::Matrix(Matrix(1., 2., 3., 4., 5., 6.))], origin.clone());
+        assert_eq!(map(&t, &rect, (1., 1.)), (9., 12.));
+        assert_eq!(map(&t, &rect, (1., 0.)), (6., 8.));
+
+        // the last op is applied first
+        let t = transform(vec![TransformOp::Translate(Vector2f(10., 0.)), TransformOp::Scale(Vector2f(2., 1.))], origin);
+        assert_eq!(map(&t, &rect, (1., 0.)), (12., 0.));
+    }
+
+    fn round_point(p: LayoutPoint) -> (f32, f32) {
+        (p.x.round(), p.y.round())
+    }
+
+    #[test]
+    fn test_linear_gradient_line() {
+        let rect = LayoutSize::new(100., 50.);
+
+        // to bottom
+        let (start, end) = linear_gradient_line(180., rect);
+        assert_eq!((round_point(start), round_point(end)), ((50., 0.), (50., 50.)));
+
+        // to right
+        let (start, end) = linear_gradient_line(90., rect);
+        assert_eq!((round_point(start), round_point(end)), ((0., 25.), (100., 25.)));
+
+        // corners get exactly the first/last color
+        let (start, end) = linear_gradient_line(45., LayoutSize::new(100., 100.));
+        assert_eq!((round_point(start), round_point(end)), ((0., 100.), (100., 0.)));
+    }
+
+    fn stop(offset: f32, color: Color) -> ColorStop {
+        ColorStop { offset, color }
+    }
+
+    fn sample(stops: &[ColorStop], t: f32) -> (u8, u8, u8, u8) {
+        let Color(r, g, b, a) = sample_stops(stops, t);
+
+        (r, g, b, a)
+    }
+
+    #[test]
+    fn test_sample_stops() {
+        let red = Color(255, 0, 0, 255);
+        let blue = Color(0, 0, 255, 255);
+        let stops = vec![stop(0.25, red.clone()), stop(0.75, blue.clone())];
+
+        assert_eq!(sample(&stops, 0.), (255, 0, 0, 255));
+        assert_eq!(sample(&stops, 0.5), (128, 0, 128, 255));
+        assert_eq!(sample(&stops, 1.), (0, 0, 255, 255));
+        assert_eq!(sample(&[], 0.5), (0, 0, 0, 0));
+
+        // hard stops
+        let stops = vec![stop(0., red.clone()), stop(0.5, red), stop(0.5, blue.clone()), stop(1., blue)];
+        assert_eq!(sample(&stops, 0.5), (255, 0, 0, 255));
+        assert_eq!(sample(&stops, 0.51), (0, 0, 255, 255));
+    }
+
+    // webrender maps the point with the inverse & tests it against the (untransformed) rect
+    #[test]
+    fn test_hit_test_transformed() {
+        let rect = LayoutRect::new(LayoutPoint::zero(), LayoutSize::new(100., 100.));
+        let t = transform(vec![TransformOp::Rotate(45.)], (Dimension::Percent(50.), Dimension::Percent(50.)));
+        let inverse = layout_transform(&t, &rect).inverse().unwrap();
+        let hit = |x, y| inverse.transform_point2d(&LayoutPoint::new(x, y)).map_or(false, |p| rect.contains(&p));
+
+        assert!(hit(50., 50.));
+        // the diamond sticks out of the original box
+        assert!(hit(50., -10.));
+        assert!(hit(105., 50.));
+        // but corners are not covered anymore
+        assert!(!hit(5., 5.));
+        assert!(!hit(95., 95.));
+    }
+
+    #[test]
+    fn test_hit_tag() {
+        assert_eq!(hit_target(hit_tag(3, None)), (3, None));
+        assert_eq!(hit_target(hit_tag(3, Some(0))), (3, Some(0)));
+        assert_eq!(hit_target(hit_tag(65535, Some(7))), (65535, Some(7)));
+    }
+
+    // what webrender does for a click into a rotated & scrolled parent (items are in absolute coords,
+    // the parent's scroll frame is inside of its reference frame)
+    #[test]
+    fn test_hit_test_rotated_parent() {
+        let mut scene = ArrayScene::new();
+        scene.set_layout_size((200., 200.));
+
+        let parent = scene.create_surface();
+        let child = scene.create_surface();
+        let sibling = scene.create_surface();
+        scene.append_child(0, parent);
+        scene.append_child(parent, child);
+        scene.append_child(parent, sibling);
+        scene.set_size(parent, Size(Dimension::Point(100.), Dimension::Point(100.)));
+        scene.set_margin(parent, Dimensions(Dimension::Point(50.), Dimension::Point(0.), Dimension::Point(0.), Dimension::Point(50.)));
+        scene.set_size(child, Size(Dimension::Point(20.), Dimension::Point(20.)));
+        scene.set_margin(child, Dimensions(Dimension::Point(20.), Dimension::Point(0.), Dimension::Point(0.), Dimension::Point(10.)));
+        scene.set_size(sibling, Size(Dimension::Point(20.), Dimension::Point(20.)));
+
+        let t = transform(vec![TransformOp::Rotate(90.)], (Dimension::Percent(50.), Dimension::Percent(50.)));
+        scene.set_transform(parent, Some(t.clone()));
+        scene.set_scroll_positions(vec![(parent, (0., 10.))]);
+        scene.calculate_layout();
+
+        let parent_rect = LayoutRect::new(LayoutPoint::new(50., 50.), LayoutSize::new(100., 100.));
+        let matrix = layout_transform(&t, &parent_rect);
+        let inverse = matrix.inverse().unwrap();
+
+        // (rect, tag) in the order of the display list
+        let items: Vec<(LayoutRect, ItemTag)> = [child, sibling]
+            .iter()
+            .map(|&s| {
+                let Rect(x, y, width, height) = scene.computed_layout(s);
+                let rect = LayoutRect::new(LayoutPoint::new(x, y), LayoutSize::new(width, height)).translate(&parent_rect.origin.to_vector());
+
+                (rect, hit_tag(s, None))
+            })
+            .collect();
+
+        let hit = |x, y| {
+            let point = inverse.transform_point2d(&LayoutPoint::new(x, y)).unwrap() + LayoutVector2D::new(0., 10.);
+
+            items.iter().rev().find(|(rect, _)| rect.contains(&point)).map(|(rect, tag)| {
+                let (surface, _) = hit_target(*tag);
+
+                (surface, round_point(point - rect.origin.to_vector()))
+            })
+        };
+
+        // centers of the child & sibling, after the scroll & the rotation
+        assert_eq!(hit(130., 70.), Some((child, (10., 10.))));
+        assert_eq!(hit(110., 60.), Some((sibling, (10., 10.))));
+        assert_eq!(hit(10., 10.), None);
+
+        // the window-relative origin we report to JS is where webrender has put the surface
+        let (x, y) = scene.absolute_origin(child);
+        let origin = matrix.transform_point2d(&LayoutPoint::new(60., 60.)).unwrap();
+        assert_eq!(round_point(LayoutPoint::new(x, y)), round_point(origin));
+        assert_eq!(round_point(origin), (140., 60.));
+    }
+}
+
 /*
 #[cfg(test)]
 mod tests {
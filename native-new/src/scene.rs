@@ -1,12 +1,15 @@
 pub use crate::api::{
-    Border, BorderRadius, BorderSide, BorderStyle, BoxShadow, Color, Flex, Flow, Image,
-    Size, Text, SurfaceId, Dimension, Dimensions, Scene, Overflow
+    Background, Border, BorderRadius, BorderSide, BorderStyle, BoxShadow, Color, Flex, Flow, Image,
+    Size, Svg, Text, TextInput, Transform, SurfaceId, Dimension, Dimensions, Scene, Overflow, WhiteSpace, Position, Display, Visibility
 };
+use crate::api::{Matrix, TransformOp, TransformOrigin};
+use crate::generated::Vector2f;
 use crate::layout::{LayoutTree, YogaTree};
 use crate::api::Rect;
 use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use crate::storage::Storage;
-use crate::text::LaidText;
+use crate::text::{align_offset, LaidText, TextInputState};
 
 /// A tree of surfaces (UI elements) along with all of their layout/visual properties
 ///
@@ -41,11 +44,29 @@ pub struct ArrayScene {
     // TODO: add vec of presence bitflags, so that we can quickly detect if surface has a border/shadow/... or not
     // 1 cache line could speed up 64 surfaces
     children: Vec<Vec<SurfaceId>>,
+    parents: Vec<Option<SurfaceId>>,
+    // scroll positions (how much is the content scrolled), they are owned by the renderer
+    scroll_positions: BTreeMap<SurfaceId, (f32, f32)>,
     border_radii: BTreeMap<SurfaceId, BorderRadius>,
-    box_shadows: BTreeMap<SurfaceId, BoxShadow>,
+    overflows: BTreeMap<SurfaceId, Overflow>,
+    positions: BTreeMap<SurfaceId, Position>,
+    displays: BTreeMap<SurfaceId, Display>,
+    visibilities: BTreeMap<SurfaceId, Visibility>,
+    // only non-default values are stored
+    opacities: BTreeMap<SurfaceId, f32>,
+    z_indices: BTreeMap<SurfaceId, i32>,
+    transforms: BTreeMap<SurfaceId, Transform>,
+    box_shadows: BTreeMap<SurfaceId, Vec<BoxShadow>>,
     background_colors: BTreeMap<SurfaceId, Color>,
+    backgrounds: BTreeMap<SurfaceId, Background>,
     texts: BTreeMap<SurfaceId, Text>,
+    text_inputs: BTreeMap<SurfaceId, TextInputState>,
+    // what is actually laid out & rendered for text inputs (value/placeholder/mask)
+    display_texts: BTreeMap<SurfaceId, Text>,
+    // last reported layout of the surfaces observed by js
+    layout_observers: BTreeMap<SurfaceId, Option<Rect>>,
     images: BTreeMap<SurfaceId, Image>,
+    svgs: BTreeMap<SurfaceId, Svg>,
     borders: BTreeMap<SurfaceId, Border>,
     layout_tree: YogaTree
 }
@@ -54,11 +75,25 @@ impl ArrayScene {
     pub fn new() -> Self {
         let mut scene = ArrayScene {
             children: vec![],
+            parents: vec![],
+            scroll_positions: BTreeMap::new(),
             border_radii: BTreeMap::new(),
+            overflows: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            displays: BTreeMap::new(),
+            visibilities: BTreeMap::new(),
+            opacities: BTreeMap::new(),
+            z_indices: BTreeMap::new(),
+            transforms: BTreeMap::new(),
             box_shadows: BTreeMap::new(),
             background_colors: BTreeMap::new(),
+            backgrounds: BTreeMap::new(),
             texts: BTreeMap::new(),
+            text_inputs: BTreeMap::new(),
+            display_texts: BTreeMap::new(),
+            layout_observers: BTreeMap::new(),
             images: BTreeMap::new(),
+            svgs: BTreeMap::new(),
             borders: BTreeMap::new(),
             layout_tree: YogaTree::new()
         };
@@ -79,10 +114,82 @@ impl ArrayScene {
         );
     }
 
+    /// Sync what the renderer has scrolled (so that absolute origins are right)
+    pub fn set_scroll_positions(&mut self, positions: Vec<(SurfaceId, (f32, f32))>) {
+        self.scroll_positions = positions.into_iter().filter(|(_, (x, y))| (*x, *y) != (0., 0.)).collect();
+    }
+
     pub fn calculate_layout(&mut self) {
         self.layout_tree.calculate();
     }
 
+    // the text block is aligned inside of the surface (the same way the renderer does it)
+    fn text_block_x(&self, surface: SurfaceId, laid_text: &LaidText) -> Option<f32> {
+        let text = self.text(surface)?;
+
+        Some(align_offset(&text.align, self.computed_layout(surface).2, laid_text.width))
+    }
+
+    /// Edit the text input & re-layout the text if what is displayed has changed
+    pub fn edit_text_input<R>(&mut self, surface: SurfaceId, f: impl FnOnce(&mut TextInputState) -> R) -> Option<R> {
+        let text_input = self.text_inputs.get_mut(&surface)?;
+        let display_text = text_input.display_text();
+        let res = f(text_input);
+
+        if text_input.display_text() != display_text {
+            self.update_text(surface);
+        }
+
+        Some(res)
+    }
+
+    /// Observed surfaces whose layout has changed since the last call (all of the new ones)
+    pub fn changed_layouts(&mut self) -> Vec<(SurfaceId, Rect)> {
+        let layout_tree = &self.layout_tree;
+
+        self.layout_observers
+            .iter_mut()
+            .filter_map(|(surface, last)| {
+                let rect = layout_tree.computed_layout(*surface);
+                let changed = match last {
+                    Some(Rect(x, y, w, h)) => (*x, *y, *w, *h) != (rect.0, rect.1, rect.2, rect.3),
+                    None => true,
+                };
+
+                match changed {
+                    true => {
+                        *last = Some(rect.clone());
+                        Some((*surface, rect))
+                    }
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    // text inputs are styled by the text but their content comes from the input
+    // whitespace is always preserved (and wrapped only if it's multiline)
+    fn update_text(&mut self, surface: SurfaceId) {
+        let display_text = match (self.texts.get(&surface), self.text_inputs.get(&surface)) {
+            (Some(text), Some(text_input)) => Some(Text {
+                color: match text_input.is_placeholder() {
+                    true => text_input.placeholder_color().unwrap_or(&text.color).clone(),
+                    false => text.color.clone(),
+                },
+                white_space: if text_input.is_multiline() { WhiteSpace::PreWrap } else { WhiteSpace::Pre },
+                spans: vec![],
+                text: text_input.display_text(),
+                ..text.clone()
+            }),
+            _ => None,
+        };
+
+        let layout_text = display_text.clone().or_else(|| self.texts.get(&surface).cloned());
+
+        self.layout_tree.set_text(surface, layout_text);
+        self.display_texts.set(surface, display_text);
+    }
+
     fn index_of(&self, parent: SurfaceId, child: SurfaceId) -> usize {
         self.children[parent]
             .iter()
@@ -97,6 +204,7 @@ impl Scene for ArrayScene {
 
         self.layout_tree.alloc();
         self.children.push(vec![]);
+        self.parents.push(None);
 
         id
     }
@@ -108,24 +216,39 @@ impl Scene for ArrayScene {
     fn append_child(&mut self, parent: SurfaceId, child: SurfaceId) {
         self.layout_tree.append_child(parent, child);
         self.children[parent].push(child);
+        self.parents[child] = Some(parent);
     }
 
     fn insert_before(&mut self, parent: SurfaceId, child: SurfaceId, before: SurfaceId) {
         let index = self.index_of(parent, before);
         self.children[parent].insert(index, child);
         self.layout_tree.insert_at(parent, child, index as u32);
+        self.parents[child] = Some(parent);
     }
 
     fn remove_child(&mut self, parent: SurfaceId, child: SurfaceId) {
         let index = self.index_of(parent, child);
         self.children[parent].remove(index);
         self.layout_tree.remove_child(parent, child);
+        self.parents[child] = None;
     }
 
     fn set_size(&mut self, surface: SurfaceId, size: Size) {
         self.layout_tree.set_size(surface, size);
     }
 
+    fn set_min_size(&mut self, surface: SurfaceId, size: Size) {
+        self.layout_tree.set_min_size(surface, size);
+    }
+
+    fn set_max_size(&mut self, surface: SurfaceId, size: Size) {
+        self.layout_tree.set_max_size(surface, size);
+    }
+
+    fn set_aspect_ratio(&mut self, surface: SurfaceId, aspect_ratio: Option<f32>) {
+        self.layout_tree.set_aspect_ratio(surface, aspect_ratio);
+    }
+
     fn set_flex(&mut self, surface: SurfaceId, flex: Flex) {
         self.layout_tree.set_flex(surface, flex);
     }
@@ -142,18 +265,86 @@ impl Scene for ArrayScene {
         self.layout_tree.set_margin(surface, margin);
     }
 
+    fn position(&self, surface: SurfaceId) -> Option<&Position> {
+        self.positions.get(&surface)
+    }
+
+    fn set_position(&mut self, surface: SurfaceId, position: Position) {
+        self.positions.insert(surface, position.clone());
+        self.layout_tree.set_position(surface, position);
+    }
+
+    fn display(&self, surface: SurfaceId) -> Display {
+        self.displays.get(&surface).cloned().unwrap_or(Display::Flex)
+    }
+
+    fn set_display(&mut self, surface: SurfaceId, display: Display) {
+        self.displays.set(surface, match display {
+            Display::Flex => None,
+            Display::None => Some(Display::None),
+        });
+        self.layout_tree.set_display(surface, display);
+    }
+
     fn computed_layout(&self, surface: SurfaceId) -> Rect {
         self.layout_tree.computed_layout(surface)
     }
 
-    fn text_layout(&self, surface: SurfaceId) -> LaidText {
+    fn text_layout(&self, surface: SurfaceId) -> Option<LaidText> {
         self.layout_tree.text_layout(surface)
     }
 
+    fn text_offset_at(&self, surface: SurfaceId, x: f32, y: f32) -> Option<usize> {
+        let laid_text = self.text_layout(surface)?;
+
+        Some(laid_text.offset_at(x - self.text_block_x(surface, &laid_text)?, y))
+    }
+
+    fn caret_rect(&self, surface: SurfaceId, offset: usize) -> Option<Rect> {
+        let laid_text = self.text_layout(surface)?;
+        let Rect(x, y, width, height) = laid_text.caret_rect(offset);
+
+        Some(Rect(x + self.text_block_x(surface, &laid_text)?, y, width, height))
+    }
+
     fn scroll_frame(&self, surface: SurfaceId) -> Option<(f32, f32)> {
         self.layout_tree.scroll_frame(surface)
     }
 
+    // going up through the parents (scroll applies to the children, transform to the surface itself)
+    fn absolute_origin(&self, surface: SurfaceId) -> (f32, f32) {
+        let mut point = (0., 0.);
+        let mut current = Some(surface);
+
+        while let Some(surface) = current {
+            let rect = self.computed_layout(surface);
+            point = (point.0 + rect.0, point.1 + rect.1);
+
+            if let Some(transform) = self.transforms.get(&surface) {
+                point = transform_point(transform, &rect, point);
+            }
+
+            current = self.parents[surface];
+
+            if let Some((x, y)) = current.and_then(|parent| self.scroll_positions.get(&parent)) {
+                point = (point.0 - x, point.1 - y);
+            }
+        }
+
+        point
+    }
+
+    fn observe_layout(&mut self, surface: SurfaceId, observe: bool) {
+        match observe {
+            true => {
+                self.layout_observers.entry(surface).or_insert(None);
+            }
+            false => {
+                self.layout_observers.remove(&surface);
+            }
+        }
+    }
+
     fn border_radius(&self, surface: SurfaceId) -> Option<&BorderRadius> {
         self.border_radii.get(&surface)
     }
@@ -162,16 +353,70 @@ impl Scene for ArrayScene {
         self.border_radii.set(surface, border_radius);
     }
 
+    fn overflow(&self, surface: SurfaceId) -> Overflow {
+        self.overflows.get(&surface).cloned().unwrap_or(Overflow::Visible)
+    }
+
     fn set_overflow(&mut self, surface: SurfaceId, overflow: Overflow) {
+        self.overflows.set(surface, match overflow {
+            Overflow::Visible => None,
+            _ => Some(overflow.clone()),
+        });
         self.layout_tree.set_overflow(surface, overflow);
     }
 
-    fn box_shadow(&self, surface: SurfaceId) -> Option<&BoxShadow> {
-        self.box_shadows.get(&surface)
+    fn opacity(&self, surface: SurfaceId) -> Option<f32> {
+        self.opacities.get(&surface).cloned()
+    }
+
+    fn set_opacity(&mut self, surface: SurfaceId, opacity: f32) {
+        self.opacities.set(surface, if opacity < 1. { Some(opacity) } else { None });
+    }
+
+    fn visibility(&self, surface: SurfaceId) -> Option<&Visibility> {
+        self.visibilities.get(&surface)
+    }
+
+    fn set_visibility(&mut self, surface: SurfaceId, visibility: Option<Visibility>) {
+        self.visibilities.set(surface, visibility);
+    }
+
+    fn is_hidden(&self, surface: SurfaceId) -> bool {
+        let mut current = Some(surface);
+
+        while let Some(surface) = current {
+            match self.visibilities.get(&surface) {
+                Some(Visibility::Hidden) => return true,
+                Some(Visibility::Visible) => return false,
+                None => current = self.parents[surface],
+            }
+        }
+
+        false
+    }
+
+    fn z_index(&self, surface: SurfaceId) -> Option<i32> {
+        self.z_indices.get(&surface).cloned()
     }
 
-    fn set_box_shadow(&mut self, surface: SurfaceId, box_shadow: Option<BoxShadow>) {
-        self.box_shadows.set(surface, box_shadow);
+    fn set_z_index(&mut self, surface: SurfaceId, z_index: i32) {
+        self.z_indices.set(surface, if z_index != 0 { Some(z_index) } else { None });
+    }
+
+    fn transform(&self, surface: SurfaceId) -> Option<&Transform> {
+        self.transforms.get(&surface)
+    }
+
+    fn set_transform(&mut self, surface: SurfaceId, transform: Option<Transform>) {
+        self.transforms.set(surface, transform);
+    }
+
+    fn box_shadows(&self, surface: SurfaceId) -> &[BoxShadow] {
+        self.box_shadows.get(&surface).map(|shadows| &shadows[..]).unwrap_or(&[])
+    }
+
+    fn set_box_shadows(&mut self, surface: SurfaceId, box_shadows: Vec<BoxShadow>) {
+        self.box_shadows.set(surface, if box_shadows.is_empty() { None } else { Some(box_shadows) });
     }
 
     fn background_color(&self, surface: SurfaceId) -> Option<&Color> {
@@ -182,6 +427,14 @@ impl Scene for ArrayScene {
         self.background_colors.set(surface, color);
     }
 
+    fn background(&self, surface: SurfaceId) -> Option<&Background> {
+        self.backgrounds.get(&surface)
+    }
+
+    fn set_background(&mut self, surface: SurfaceId, background: Option<Background>) {
+        self.backgrounds.set(surface, background);
+    }
+
     fn image(&self, surface: SurfaceId) -> Option<&Image> {
         self.images.get(&surface)
     }
@@ -190,13 +443,50 @@ impl Scene for ArrayScene {
         self.images.set(surface, image);
     }
 
+    fn svg(&self, surface: SurfaceId) -> Option<&Svg> {
+        self.svgs.get(&surface)
+    }
+
+    fn set_svg(&mut self, surface: SurfaceId, svg: Option<Svg>) {
+        let removed = svg.is_none() && self.svgs.contains_key(&surface);
+
+        self.svgs.set(surface, svg.clone());
+        self.layout_tree.set_svg(surface, svg);
+
+        // text might have been replaced by the svg
+        if removed && self.texts.contains_key(&surface) {
+            self.update_text(surface);
+        }
+    }
+
     fn text(&self, surface: SurfaceId) -> Option<&Text> {
-        self.texts.get(&surface)
+        self.display_texts.get(&surface).or_else(|| self.texts.get(&surface))
     }
 
     fn set_text(&mut self, surface: SurfaceId, text: Option<Text>) {
-        self.texts.set(surface, text.clone());
-        self.layout_tree.set_text(surface, text);
+        self.texts.set(surface, text);
+        self.update_text(surface);
+    }
+
+    fn text_input(&self, surface: SurfaceId) -> Option<&TextInputState> {
+        self.text_inputs.get(&surface)
+    }
+
+    // editing state is kept if the input is only updated
+    fn set_text_input(&mut self, surface: SurfaceId, text_input: Option<TextInput>) {
+        match text_input {
+            Some(text_input) => match self.text_inputs.entry(surface) {
+                Entry::Occupied(mut e) => e.get_mut().set_text_input(text_input),
+                Entry::Vacant(e) => {
+                    e.insert(TextInputState::new(text_input));
+                }
+            },
+            None => {
+                self.text_inputs.remove(&surface);
+            }
+        }
+
+        self.update_text(surface);
     }
 
     fn border(&self, surface: SurfaceId) -> Option<&Border> {
@@ -209,6 +499,33 @@ impl Scene for ArrayScene {
     }
 }
 
+// map the point (in the parent space) like the renderer does (see `layout_transform`)
+// ops are applied in reverse order, around the origin (relative to the rect)
+fn transform_point(transform: &Transform, Rect(x, y, width, height): &Rect, point: (f32, f32)) -> (f32, f32) {
+    let resolve = |dimension: &Dimension, size: f32| match dimension {
+        Dimension::Auto => 0.,
+        Dimension::Point(v) => *v,
+        Dimension::Percent(p) => size * p / 100.,
+    };
+    let TransformOrigin(origin_x, origin_y) = &transform.origin;
+    let origin = (x + resolve(origin_x, *width), y + resolve(origin_y, *height));
+    let (px, py) = (point.0 - origin.0, point.1 - origin.1);
+
+    let (px, py) = transform.ops.iter().rev().fold((px, py), |(x, y), op| match op {
+        TransformOp::Matrix(Matrix(a, b, c, d, e, f)) => (a * x + c * y + e, b * x + d * y + f),
+        TransformOp::Translate(Vector2f(tx, ty)) => (x + tx, y + ty),
+        TransformOp::Scale(Vector2f(sx, sy)) => (x * sx, y * sy),
+        TransformOp::Rotate(deg) => {
+            let (sin, cos) = deg.to_radians().sin_cos();
+
+            (x * cos - y * sin, x * sin + y * cos)
+        }
+        TransformOp::Skew(Vector2f(ax, ay)) => (x + ax.to_radians().tan() * y, ay.to_radians().tan() * x + y),
+    });
+
+    (px + origin.0, py + origin.1)
+}
+
 // TODO
 /*
 impl<'a> Debug for SurfaceData<'a> {
@@ -289,3 +606,128 @@ mod tests {
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(x: f32, y: f32) -> Transform {
+        Transform {
+            ops: vec![TransformOp::Translate(Vector2f(x, y))],
+            origin: TransformOrigin(Dimension::Percent(50.), Dimension::Percent(50.)),
+        }
+    }
+
+    fn round((x, y): (f32, f32)) -> (f32, f32) {
+        ((x * 1000.).round() / 1000., (y * 1000.).round() / 1000.)
+    }
+
+    #[test]
+    fn test_transform_point() {
+        let rect = Rect(0., 0., 100., 100.);
+        let transform = |ops, x, y| Transform { ops, origin: TransformOrigin(Dimension::Point(x), Dimension::Point(y)) };
+
+        // around the center
+        let t = transform(vec![TransformOp::Scale(Vector2f(2., 2.))], 50., 50.);
+        assert_eq!(round(transform_point(&t, &rect, (0., 0.))), (-50., -50.));
+
+        // clockwise (y is down)
+        let t = transform(vec![TransformOp::Rotate(90.)], 0., 0.);
+        assert_eq!(round(transform_point(&t, &rect, (10., 0.))), (0., 10.));
+
+        let t = transform(vec![TransformOp::Matrix(Matrix(1., 2., 3., 4., 5., 6.))], 0., 0.);
+        assert_eq!(round(transform_point(&t, &rect, (1., 1.))), (9., 12.));
+
+        // the last op is applied first
+        let t = transform(vec![TransformOp::Translate(Vector2f(10., 0.)), TransformOp::Scale(Vector2f(2., 1.))], 0., 0.);
+        assert_eq!(round(transform_point(&t, &rect, (1., 0.))), (12., 0.));
+    }
+
+    #[test]
+    fn test_absolute_origin() {
+        let mut scene = ArrayScene::new();
+        scene.set_layout_size((200., 200.));
+
+        let parent = scene.create_surface();
+        let child = scene.create_surface();
+        scene.append_child(0, parent);
+        scene.append_child(parent, child);
+        scene.set_size(parent, Size(Dimension::Point(100.), Dimension::Point(100.)));
+        scene.set_size(child, Size(Dimension::Point(10.), Dimension::Point(10.)));
+        scene.set_margin(child, Dimensions(Dimension::Point(20.), Dimension::Point(0.), Dimension::Point(0.), Dimension::Point(10.)));
+        scene.calculate_layout();
+
+        assert_eq!(scene.absolute_origin(child), (10., 20.));
+
+        // scroll of the parent moves the child but not the parent
+        scene.set_scroll_positions(vec![(parent, (0., 30.))]);
+        assert_eq!(scene.absolute_origin(parent), (0., 0.));
+        assert_eq!(scene.absolute_origin(child), (10., -10.));
+
+        // transforms of both
+        scene.set_transform(parent, Some(translate(5., 5.)));
+        scene.set_transform(child, Some(translate(1., 0.)));
+        assert_eq!(scene.absolute_origin(child), (16., -5.));
+    }
+
+    #[test]
+    fn test_display_none() {
+        let mut scene = ArrayScene::new();
+        scene.set_layout_size((200., 200.));
+
+        let first = scene.create_surface();
+        let second = scene.create_surface();
+        scene.append_child(0, first);
+        scene.append_child(0, second);
+        scene.set_size(first, Size(Dimension::Point(10.), Dimension::Point(10.)));
+        scene.set_size(second, Size(Dimension::Point(10.), Dimension::Point(10.)));
+
+        // invisible still takes the space
+        scene.set_visibility(first, Some(Visibility::Hidden));
+        scene.calculate_layout();
+        assert_eq!(scene.absolute_origin(second), (0., 10.));
+
+        scene.set_display(first, Display::None);
+        scene.calculate_layout();
+        assert_eq!(scene.absolute_origin(second), (0., 0.));
+
+        scene.set_display(first, Display::Flex);
+        scene.calculate_layout();
+        assert_eq!(scene.absolute_origin(second), (0., 10.));
+    }
+
+    #[test]
+    fn test_visibility() {
+        let mut scene = ArrayScene::new();
+
+        let parent = scene.create_surface();
+        let child = scene.create_surface();
+        let grandchild = scene.create_surface();
+        scene.append_child(0, parent);
+        scene.append_child(parent, child);
+        scene.append_child(child, grandchild);
+
+        assert!(!scene.is_hidden(grandchild));
+
+        // inherited
+        scene.set_visibility(parent, Some(Visibility::Hidden));
+        assert!(scene.is_hidden(parent));
+        assert!(scene.is_hidden(child));
+        assert!(scene.is_hidden(grandchild));
+
+        // visible child of a hidden parent (like in CSS)
+        scene.set_visibility(child, Some(Visibility::Visible));
+        assert!(scene.is_hidden(parent));
+        assert!(!scene.is_hidden(child));
+        assert!(!scene.is_hidden(grandchild));
+
+        // removed from the hidden parent
+        scene.set_visibility(child, None);
+        scene.remove_child(parent, child);
+        assert!(!scene.is_hidden(child));
+
+        scene.set_visibility(parent, None);
+        assert!(!scene.is_hidden(parent));
+        assert!(scene.visibility(parent).is_none());
+    }
+}
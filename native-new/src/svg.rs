@@ -0,0 +1,118 @@
+use crate::api::Svg;
+use image::RgbaImage;
+use nsvg::{SvgImage, Units};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Parsing & rasterization of vector icons
+///
+/// nanosvg is far from complete (no text, no filters) but it's small, fast and
+/// good enough for icons which is what this is for. If we ever need more, resvg
+/// should be a drop-in replacement here.
+///
+/// Both layout (intrinsic size) and renderer (pixels) need the parsed image but
+/// parsing is cheap compared to rasterization (which is cached in the renderer)
+///
+/// The source comes from js so it can be invalid, nothing is drawn then
+pub fn parse(svg: &Svg) -> Result<SvgImage, nsvg::Error> {
+    match svg {
+        Svg::Source(source) => nsvg::parse_str(source, Units::Pixel, DPI),
+        Svg::Path(path) => nsvg::parse_file(Path::new(path), Units::Pixel, DPI),
+    }
+}
+
+/// Intrinsic size (width, height) which is taken from the viewBox (or width/height attrs)
+///
+/// Invalid svgs have no size
+pub fn intrinsic_size(svg: &Svg) -> (f32, f32) {
+    match parse(svg) {
+        Ok(image) => (image.width(), image.height()),
+        Err(err) => {
+            error!("couldn't parse svg {:?}", err);
+
+            (0., 0.)
+        }
+    }
+}
+
+/// Rasterize to fit the given size (in device pixels) so it's always sharp, regardless of dpi
+///
+/// nanosvg can only scale uniformly so the aspect ratio is kept and the result
+/// might be smaller in one dimension (it should be centered, like `xMidYMid meet`)
+pub fn rasterize(svg: &Svg, width: u32, height: u32) -> Option<RgbaImage> {
+    let image = parse(svg).map_err(|err| error!("couldn't parse svg {:?}", err)).ok()?;
+    let scale = fit_scale((image.width(), image.height()), (width, height))?;
+
+    image.rasterize(scale).map_err(|err| error!("couldn't rasterize svg {:?}", err)).ok()
+}
+
+/// Key for the raster cache (so that we don't have to keep the whole source)
+pub fn cache_key(svg: &Svg) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    match svg {
+        Svg::Source(source) => (0, source).hash(&mut hasher),
+        Svg::Path(path) => (1, path).hash(&mut hasher),
+    }
+
+    hasher.finish()
+}
+
+// there's nothing to draw if either of the sizes is empty
+fn fit_scale((svg_width, svg_height): (f32, f32), (width, height): (u32, u32)) -> Option<f32> {
+    let scale = (width as f32 / svg_width).min(height as f32 / svg_height);
+
+    match scale.is_finite() && (scale > 0.) {
+        true => Some(scale),
+        false => None,
+    }
+}
+
+// units are pixels so this only matters for svgs using physical units (mm, in, ...)
+static DPI: f32 = 96.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ICON: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 10"><rect width="20" height="10" fill="red"/></svg>"#;
+
+    #[test]
+    fn test_intrinsic_size() {
+        assert_eq!(intrinsic_size(&Svg::Source(ICON.to_string())), (20., 10.));
+        assert_eq!(intrinsic_size(&Svg::Source("<not svg".to_string())), (0., 0.));
+        assert_eq!(intrinsic_size(&Svg::Path("/does/not/exist.svg".to_string())), (0., 0.));
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let svg = Svg::Source(ICON.to_string());
+
+        // aspect ratio is kept
+        let image = rasterize(&svg, 40, 40).unwrap();
+        assert_eq!((image.width(), image.height()), (40, 20));
+
+        // nothing to draw
+        assert!(rasterize(&svg, 0, 40).is_none());
+        assert!(rasterize(&svg, 40, 0).is_none());
+        assert!(rasterize(&Svg::Source("<not svg".to_string()), 40, 40).is_none());
+    }
+
+    #[test]
+    fn test_fit_scale() {
+        assert_eq!(fit_scale((20., 10.), (40, 40)), Some(2.));
+        assert_eq!(fit_scale((20., 10.), (10, 40)), Some(0.5));
+        assert_eq!(fit_scale((0., 10.), (10, 10)), Some(1.));
+        assert_eq!(fit_scale((0., 0.), (10, 10)), None);
+        assert_eq!(fit_scale((20., 10.), (0, 10)), None);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let a = Svg::Source(ICON.to_string());
+
+        assert_eq!(cache_key(&a), cache_key(&a.clone()));
+        assert_ne!(cache_key(&a), cache_key(&Svg::Path(ICON.to_string())));
+    }
+}
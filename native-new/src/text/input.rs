@@ -0,0 +1,508 @@
+use crate::api::{Color, TextInput};
+use std::time::Instant;
+
+/// Editing state of a text input (value, caret, selection, undo/redo)
+///
+/// It knows nothing about the layout, moving between lines (& clicking) is done
+/// by the window (with the help of `LaidText`) which then just calls `move_to()`
+///
+/// Offsets are in chars (not bytes) so they can be used with `LaidText` directly
+/// (and they are also code points in js)
+pub struct TextInputState {
+    text_input: TextInput,
+    caret: usize,
+    // selection is between the anchor & the caret
+    anchor: usize,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    // consecutive typing is undone at once (like in browsers)
+    typing: bool,
+    // caret blinks since then
+    focused_at: Option<Instant>,
+}
+
+#[derive(Clone, Debug)]
+struct Snapshot {
+    value: String,
+    caret: usize,
+}
+
+impl TextInputState {
+    pub fn new(text_input: TextInput) -> Self {
+        let caret = text_input.value.chars().count();
+
+        TextInputState {
+            text_input,
+            caret,
+            anchor: caret,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            typing: false,
+            focused_at: None,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.text_input.value
+    }
+
+    /// Update from js, the value is replaced only if it's different
+    /// (which is not the case after `TextChanged` so the caret & undo are kept)
+    pub fn set_text_input(&mut self, text_input: TextInput) {
+        let len = text_input.value.chars().count();
+
+        if text_input.value != self.text_input.value {
+            self.caret = self.caret.min(len);
+            self.anchor = self.anchor.min(len);
+            self.typing = false;
+        }
+
+        self.text_input = text_input;
+    }
+
+    pub fn placeholder_color(&self) -> Option<&Color> {
+        self.text_input.placeholder_color.as_ref()
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.text_input.multiline
+    }
+
+    pub fn is_placeholder(&self) -> bool {
+        self.text_input.value.is_empty()
+    }
+
+    /// What should be laid out & rendered instead of the text
+    pub fn display_text(&self) -> String {
+        match (self.is_placeholder(), self.text_input.secure) {
+            (true, _) => self.text_input.placeholder.clone(),
+            (false, true) => "•".repeat(self.len()),
+            (false, false) => self.text_input.value.clone(),
+        }
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    // (start, end), it's empty if there's no selection
+    pub fn selection(&self) -> (usize, usize) {
+        (self.anchor.min(self.caret), self.anchor.max(self.caret))
+    }
+
+    // nothing for passwords
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection();
+
+        if (start == end) || self.text_input.secure {
+            return None;
+        }
+
+        Some(self.text_input.value.chars().skip(start).take(end - start).collect())
+    }
+
+    /// Remove the selection & return what was removed (nothing for passwords)
+    pub fn cut(&mut self) -> Option<String> {
+        let selected = self.selected_text()?;
+        let (start, end) = self.selection();
+
+        self.replace(start, end, "", false);
+
+        Some(selected)
+    }
+
+    pub fn focus(&mut self) {
+        self.focused_at = Some(Instant::now());
+    }
+
+    pub fn blur(&mut self) {
+        self.focused_at = None;
+        self.anchor = self.caret;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused_at.is_some()
+    }
+
+    // on for the first half of the period (and restarted after any caret change)
+    pub fn caret_visible(&self, now: Instant) -> bool {
+        match self.focused_at {
+            Some(since) => ((now.duration_since(since).as_millis() / BLINK_MS) % 2) == 0,
+            None => false,
+        }
+    }
+
+    pub fn move_to(&mut self, offset: usize, select: bool) {
+        self.caret = offset.min(self.len());
+
+        if !select {
+            self.anchor = self.caret;
+        }
+
+        self.typing = false;
+        self.restart_blink();
+    }
+
+    // collapses the selection first (unless it's being extended)
+    pub fn move_left(&mut self, word: bool, select: bool) {
+        let (start, end) = self.selection();
+
+        let offset = match (word, select, start != end) {
+            (false, false, true) => start,
+            (true, _, _) => self.prev_word(self.caret),
+            _ => self.caret.saturating_sub(1),
+        };
+
+        self.move_to(offset, select);
+    }
+
+    pub fn move_right(&mut self, word: bool, select: bool) {
+        let (start, end) = self.selection();
+
+        let offset = match (word, select, start != end) {
+            (false, false, true) => end,
+            (true, _, _) => self.next_word(self.caret),
+            _ => self.caret + 1,
+        };
+
+        self.move_to(offset, select);
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.len();
+        self.restart_blink();
+    }
+
+    /// Replace the selection (or insert at the caret), newlines are replaced
+    /// with spaces if it's not multiline
+    pub fn insert(&mut self, text: &str) -> bool {
+        let text = match self.text_input.multiline {
+            true => text.to_string(),
+            false => text.replace(|ch: char| (ch == '\n') || (ch == '\r'), " "),
+        };
+        let (start, end) = self.selection();
+
+        if text.is_empty() && (start == end) {
+            return false;
+        }
+
+        let typing = (text.chars().count() == 1) && !text.contains(char::is_whitespace);
+        self.replace(start, end, &text, typing);
+
+        true
+    }
+
+    pub fn delete_backward(&mut self, word: bool) -> bool {
+        let (start, end) = self.selection();
+
+        if start != end {
+            self.replace(start, end, "", false);
+        } else if self.caret != 0 {
+            let start = if word { self.prev_word(self.caret) } else { self.caret - 1 };
+            self.replace(start, self.caret, "", false);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    pub fn delete_forward(&mut self, word: bool) -> bool {
+        let (start, end) = self.selection();
+
+        if start != end {
+            self.replace(start, end, "", false);
+        } else if self.caret != self.len() {
+            let end = if word { self.next_word(self.caret) } else { self.caret + 1 };
+            self.replace(self.caret, end, "", false);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo_stack.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.redo_stack.push(current);
+                self.restore(snapshot);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(snapshot) => {
+                let current = self.snapshot();
+                self.undo_stack.push(current);
+                self.restore(snapshot);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    // start of the word before the offset
+    pub fn prev_word(&self, offset: usize) -> usize {
+        let chars: Vec<char> = self.text_input.value.chars().collect();
+        let mut i = offset.min(chars.len());
+
+        while (i > 0) && !is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+
+        while (i > 0) && is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+
+        i
+    }
+
+    // end of the word after the offset
+    pub fn next_word(&self, offset: usize) -> usize {
+        let chars: Vec<char> = self.text_input.value.chars().collect();
+        let mut i = offset.min(chars.len());
+
+        while (i < chars.len()) && !is_word_char(chars[i]) {
+            i += 1;
+        }
+
+        while (i < chars.len()) && is_word_char(chars[i]) {
+            i += 1;
+        }
+
+        i
+    }
+
+    fn len(&self) -> usize {
+        self.text_input.value.chars().count()
+    }
+
+    fn replace(&mut self, start: usize, end: usize, text: &str, typing: bool) {
+        // typing is coalesced until the caret is moved (or something else is done)
+        if !(typing && self.typing) {
+            let snapshot = self.snapshot();
+            self.undo_stack.push(snapshot);
+        }
+
+        let value = &self.text_input.value;
+        let (start_byte, end_byte) = (byte_offset(value, start), byte_offset(value, end));
+
+        self.text_input.value.replace_range(start_byte..end_byte, text);
+        self.redo_stack.clear();
+        self.move_to(start + text.chars().count(), false);
+        self.typing = typing;
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            value: self.text_input.value.clone(),
+            caret: self.caret,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.text_input.value = snapshot.value;
+        self.move_to(snapshot.caret, false);
+    }
+
+    fn restart_blink(&mut self) {
+        if self.focused_at.is_some() {
+            self.focused_at = Some(Instant::now());
+        }
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || (ch == '_')
+}
+
+fn byte_offset(s: &str, offset: usize) -> usize {
+    s.char_indices().nth(offset).map(|(i, _)| i).unwrap_or(s.len())
+}
+
+static BLINK_MS: u128 = 500;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn input(value: &str) -> TextInputState {
+        TextInputState::new(TextInput {
+            value: value.into(),
+            placeholder: "Type here".into(),
+            placeholder_color: None,
+            secure: false,
+            multiline: false,
+        })
+    }
+
+    #[test]
+    fn test_insert_delete() {
+        let mut i = input("");
+        assert!(i.is_placeholder());
+        assert_eq!(i.display_text(), "Type here");
+
+        assert!(i.insert("h"));
+        assert!(i.insert("ěllo"));
+        assert_eq!(i.value(), "hěllo");
+        assert_eq!(i.caret(), 5);
+
+        i.move_to(1, false);
+        assert!(i.delete_backward(false));
+        assert!(i.delete_forward(false));
+        assert_eq!(i.value(), "llo");
+        assert_eq!(i.caret(), 0);
+
+        assert!(!i.delete_backward(false));
+        i.move_to(100, false);
+        assert!(!i.delete_forward(false));
+        assert_eq!(i.caret(), 3);
+
+        // newlines only if multiline
+        i.insert("a\nb");
+        assert_eq!(i.value(), "lloa b");
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut i = input("Hello world");
+
+        i.move_to(0, false);
+        i.move_right(true, true);
+        assert_eq!(i.selection(), (0, 5));
+        assert_eq!(i.selected_text(), Some("Hello".into()));
+
+        // collapsed to the end
+        i.move_right(false, false);
+        assert_eq!(i.selection(), (5, 5));
+
+        i.select_all();
+        assert!(i.insert("Hi"));
+        assert_eq!(i.value(), "Hi");
+
+        i.select_all();
+        assert!(i.delete_backward(false));
+        assert_eq!(i.value(), "");
+    }
+
+    #[test]
+    fn test_words() {
+        let mut i = input("foo, bar_baz  qux");
+
+        assert_eq!(i.prev_word(17), 14);
+        assert_eq!(i.prev_word(14), 5);
+        assert_eq!(i.prev_word(3), 0);
+        assert_eq!(i.next_word(0), 3);
+        assert_eq!(i.next_word(3), 12);
+
+        i.move_to(12, false);
+        assert!(i.delete_backward(true));
+        assert_eq!(i.value(), "foo,   qux");
+
+        i.move_to(0, false);
+        assert!(i.delete_forward(true));
+        assert_eq!(i.value(), ",   qux");
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut i = input("");
+
+        for ch in "Hello world".chars() {
+            i.insert(&ch.to_string());
+        }
+        i.delete_backward(false);
+        assert_eq!(i.value(), "Hello worl");
+
+        assert!(i.undo());
+        assert_eq!(i.value(), "Hello world");
+
+        // typing is undone per word
+        assert!(i.undo());
+        assert_eq!(i.value(), "Hello ");
+        assert!(i.undo());
+        assert_eq!(i.value(), "Hello");
+        assert!(i.undo());
+        assert_eq!(i.value(), "");
+        assert!(!i.undo());
+
+        assert!(i.redo());
+        assert_eq!(i.value(), "Hello");
+        assert_eq!(i.caret(), 5);
+
+        // new edit clears the redo
+        i.insert("!");
+        assert!(!i.redo());
+    }
+
+    #[test]
+    fn test_secure() {
+        let mut i = input("secret");
+        i.text_input.secure = true;
+
+        assert_eq!(i.display_text(), "••••••");
+
+        i.select_all();
+        assert_eq!(i.selected_text(), None);
+    }
+
+    #[test]
+    fn test_cut() {
+        let mut i = input("Hello world");
+
+        // nothing selected, nothing removed
+        assert_eq!(i.cut(), None);
+        assert_eq!(i.value(), "Hello world");
+
+        i.move_to(5, false);
+        i.move_to(11, true);
+        assert_eq!(i.cut(), Some(" world".into()));
+        assert_eq!(i.value(), "Hello");
+        assert_eq!(i.caret(), 5);
+
+        // passwords can't be cut
+        i.text_input.secure = true;
+        i.select_all();
+        assert_eq!(i.cut(), None);
+        assert_eq!(i.value(), "Hello");
+    }
+
+    #[test]
+    fn test_set_text_input() {
+        let mut i = input("Hello world");
+        assert_eq!(i.caret(), 11);
+
+        let mut text_input = i.text_input.clone();
+        text_input.value = "Hi".into();
+        i.set_text_input(text_input);
+
+        assert_eq!(i.value(), "Hi");
+        assert_eq!(i.caret(), 2);
+    }
+
+    #[test]
+    fn test_blink() {
+        let mut i = input("");
+        let now = Instant::now();
+
+        assert!(!i.caret_visible(now));
+
+        i.focus();
+        let since = i.focused_at.unwrap();
+        assert!(i.caret_visible(since));
+        assert!(!i.caret_visible(since + Duration::from_millis(600)));
+        assert!(i.caret_visible(since + Duration::from_millis(1100)));
+
+        i.blur();
+        assert!(!i.is_focused());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::api::{Text};
+use crate::api::{Rect, Text, TextAlign, WhiteSpace};
+use crate::generated::{GlyphBox, TextLayout, TextLine};
 
 /// Measuring & laying the glyphs might seem a bit orthogonal but to measure we need to know
 /// a lot of what is then also needed for glyph positioning so it makes sense to do it just once.
@@ -13,27 +14,253 @@ pub trait TextLayoutAlgo {
     fn layout_text(&mut self, text: &Text, max_width: Option<f32>) -> LaidText;
 }
 
-// TODO: generate (so it can be sent to js)
+// internal, it's converted to `TextLayout` when it's sent to js
 #[derive(Clone, Debug)]
 pub struct LaidText {
     pub lines: i32,
     pub width: f32,
-    pub glyphs: Vec<LaidGlyph>
+    // from the top of the line
+    pub baseline: f32,
+    pub metrics: TextMetrics,
+    // fonts used by glyphs
+    pub fonts: Vec<LaidFont>,
+    pub glyphs: Vec<LaidGlyph>,
+    // some lines were skipped or ellipsized
+    pub truncated: bool,
+    // (aligned) extents of each line
+    pub line_rects: Vec<Rect>,
+    // char offset (in the original text) where each line starts
+    pub line_starts: Vec<usize>,
+    // (span index, rect) for each line of each span (for links & underlines)
+    pub span_rects: Vec<(usize, Rect)>
+}
+
+/// Font metrics (of the text font, not spans) so that the renderer can place decorations,
+/// offsets are relative to the baseline (positive is down) and they are for the top edge
+#[derive(Clone, Debug)]
+pub struct TextMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub underline_offset: f32,
+    pub underline_thickness: f32,
+    pub strikethrough_offset: f32,
+    pub strikethrough_thickness: f32
+}
+
+/// Font which was actually used for shaping (it might be a fallback for emoji, CJK, ...)
+/// renderer has to use the same one, otherwise glyph indices would point to different glyphs
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaidFont {
+    pub family: String,
+    pub size: f32,
+    pub bold: bool,
+    pub italic: bool,
+    // file & face index if we know it (family alone could be resolved to a different file)
+    pub file: Option<(String, u32)>
 }
 
 #[derive(Clone)]
 pub struct LaidGlyph {
     pub glyph_index: u32,
+    // index in LaidText.fonts
+    pub font: usize,
     pub x: f32,
-    pub y: f32
+    pub y: f32,
+    pub advance: f32,
+    pub line: usize,
+    // char range (in the original text) of the cluster the glyph belongs to
+    // (more chars can be one glyph, ie. ligatures & one char can be more glyphs)
+    pub cluster: (usize, usize),
+    // glyphs are always in visual order but rtl clusters start on the right
+    pub rtl: bool,
+    // index of the (last) span containing the glyph
+    pub span: Option<usize>
 }
 
 impl Debug for LaidGlyph {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "{:?} ", (self.glyph_index, self.x, self.y))
+        write!(f, "{:?} ", (self.glyph_index, self.font, self.x, self.y, self.cluster, self.span))
+    }
+}
+
+impl LaidGlyph {
+    // (x, offset) of the left & right edge
+    fn edges(&self) -> [(f32, usize); 2] {
+        let (start, end) = self.cluster;
+        let right = self.x + self.advance;
+
+        match self.rtl {
+            false => [(self.x, start), (right, end)],
+            true => [(self.x, end), (right, start)],
+        }
+    }
+}
+
+/// Hit-testing & carets, everything is relative to the text block (like the glyphs)
+impl LaidText {
+    /// Char offset of the nearest cluster edge on the line under `y`
+    pub fn offset_at(&self, x: f32, y: f32) -> usize {
+        let line = match self.line_rects.iter().position(|r| y < (r.1 + r.3)) {
+            Some(line) => line,
+            None => self.line_rects.len().saturating_sub(1),
+        };
+        let mut nearest = (f32::MAX, self.line_starts.get(line).cloned().unwrap_or(0));
+
+        for glyph in self.glyphs.iter().filter(|g| g.line == line) {
+            for (edge_x, offset) in glyph.edges().iter() {
+                let distance = (x - edge_x).abs();
+
+                if distance < nearest.0 {
+                    nearest = (distance, *offset);
+                }
+            }
+        }
+
+        nearest.1
+    }
+
+    /// Zero-width rect (of the line height) where the caret before the char at `offset` should be
+    ///
+    /// Offset at the end of a wrapped line is at the start of the next one (like in browsers)
+    pub fn caret_rect(&self, offset: usize) -> Rect {
+        let line = self.line_starts.iter().rposition(|s| *s <= offset).unwrap_or(0);
+        let line_rect = match self.line_rects.get(line) {
+            Some(r) => r,
+            None => return Rect(0., 0., 0., 0.),
+        };
+        let glyphs = || self.glyphs.iter().filter(|g| g.line == line);
+
+        // the first edge with that offset or the glyph containing it (in the middle of a ligature)
+        // empty lines have no glyphs
+        let x = glyphs()
+            .flat_map(|g| g.edges().to_vec())
+            .find(|(_, o)| *o == offset)
+            .map(|(x, _)| x)
+            .or_else(|| glyphs().find(|g| (g.cluster.0 < offset) && (offset < g.cluster.1)).map(|g| g.x))
+            .unwrap_or(line_rect.0);
+
+        Rect(x, line_rect.1, 0., line_rect.3)
+    }
+
+    /// Rects (one per line) covering glyphs of the chars between `start` and `end`
+    ///
+    /// Mixed-direction lines get one rect spanning all of the selected glyphs, which is good enough
+    pub fn selection_rects(&self, start: usize, end: usize) -> Vec<Rect> {
+        let mut rects = vec![];
+
+        for (line, line_rect) in self.line_rects.iter().enumerate() {
+            let xs = self.glyphs
+                .iter()
+                .filter(|g| (g.line == line) && (g.cluster.0 < end) && (g.cluster.1 > start))
+                .fold(None, |acc: Option<(f32, f32)>, g| {
+                    let (min, max) = acc.unwrap_or((g.x, g.x + g.advance));
+
+                    Some((min.min(g.x), max.max(g.x + g.advance)))
+                });
+
+            if let Some((min, max)) = xs {
+                rects.push(Rect(min, line_rect.1, max - min, line_rect.3));
+            }
+        }
+
+        rects
+    }
+}
+
+/// What js gets (line rects & glyph boxes instead of glyph indices & fonts)
+impl Into<TextLayout> for LaidText {
+    fn into(self) -> TextLayout {
+        let LaidText { width, baseline, metrics, truncated, line_rects, line_starts, glyphs, .. } = self;
+
+        TextLayout {
+            width,
+            height: line_rects.last().map(|Rect(_, y, _, h)| y + h).unwrap_or(0.),
+            baseline,
+            ascent: metrics.ascent,
+            descent: metrics.descent,
+            truncated,
+            lines: line_rects
+                .iter()
+                .zip(line_starts)
+                .map(|(rect, start)| TextLine { rect: rect.clone(), start, baseline: rect.1 + baseline })
+                .collect(),
+            glyphs: glyphs
+                .iter()
+                .map(|g| {
+                    let Rect(_, y, _, height) = line_rects[g.line];
+
+                    GlyphBox { rect: Rect(g.x, y, g.advance, height), line: g.line, start: g.cluster.0, end: g.cluster.1 }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How much should something of `width` be moved to be aligned in `available` space
+///
+/// Used for both lines (inside of the text block) and for the whole block (inside of the rect)
+/// `Justify` is done by pango (by stretching spaces) so there's nothing to offset
+pub fn align_offset(align: &TextAlign, available: f32, width: f32) -> f32 {
+    match align {
+        TextAlign::Left | TextAlign::Justify => 0.,
+        TextAlign::Center => (available - width) / 2.,
+        TextAlign::Right => available - width,
+    }
+}
+
+/// Collapses whitespace (including newlines, unless it's `PreLine`) like the browser does,
+/// leading & trailing whitespace (of each line) is removed too
+///
+/// Returns also byte offsets (in the result) for every char of the original text
+/// (and one for the end) so that spans can be mapped
+pub fn collapse_white_space(text: &str, white_space: &WhiteSpace) -> (String, Vec<usize>) {
+    let keep_newlines = match white_space {
+        WhiteSpace::Pre | WhiteSpace::PreWrap => {
+            let offsets = text.char_indices().map(|(i, _)| i).chain(Some(text.len())).collect();
+
+            return (text.to_string(), offsets);
+        }
+        WhiteSpace::Normal | WhiteSpace::NoWrap => false,
+        WhiteSpace::PreLine => true,
+    };
+
+    let mut res = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut pending_space = false;
+
+    for ch in text.chars() {
+        if (ch == '\n') && keep_newlines {
+            pending_space = false;
+            offsets.push(res.len());
+            res.push(ch);
+        } else if ch.is_whitespace() {
+            pending_space = true;
+            offsets.push(res.len());
+        } else {
+            if pending_space && !res.is_empty() && !res.ends_with('\n') {
+                res.push(' ');
+            }
+
+            pending_space = false;
+            offsets.push(res.len());
+            res.push(ch);
+        }
     }
+
+    offsets.push(res.len());
+
+    (res, offsets)
+}
+
+/// There are only regular & bold faces for now
+pub fn is_bold(font_weight: u32) -> bool {
+    font_weight >= 600
 }
+
 mod pango;
 pub use self::pango::PangoService;
 
+mod input;
+pub use self::input::TextInputState;
+
 use std::fmt::{Debug, Formatter};
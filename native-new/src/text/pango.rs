@@ -1,9 +1,10 @@
-use super::{LaidGlyph, LaidText, TextLayoutAlgo};
-use crate::api::{Text, TextAlign};
+use super::{align_offset, collapse_white_space, is_bold, LaidFont, LaidGlyph, LaidText, TextLayoutAlgo, TextMetrics};
+use crate::api::{Direction, Ellipsize, FontFeature, FontStyle, FontVariation, Rect, Text, TextAlign, WhiteSpace, WordBreak};
 use pango::prelude::*;
-use pango::{Alignment, WrapMode};
+use pango::{Alignment, AttrList, Attribute, EllipsizeMode, Style, Weight, WrapMode};
 use pangocairo::FontMap;
 use pango_sys::*;
+use std::ffi::CStr;
 
 pub struct PangoService {
     pango_context: pango::Context,
@@ -18,80 +19,377 @@ impl PangoService {
         PangoService { pango_context }
     }
 
-    fn get_layout(&self, text: &Text) -> pango::Layout {
-        let mut description = pango::FontDescription::new();
-        description.set_family("Arial");
-        description.set_absolute_size(to_scale_f64(text.font_size));
+    // returns also byte ranges of the spans & byte offsets of the original chars (in the collapsed text)
+    fn get_layout(&self, text: &Text) -> (pango::Layout, Vec<(usize, usize)>, Vec<usize>) {
+        let (collapsed, offsets) = collapse_white_space(&text.text, &text.white_space);
+        let span_ranges: Vec<(usize, usize)> = text
+            .spans
+            .iter()
+            .map(|s| {
+                let offset = |i: u32| offsets[(i as usize).min(offsets.len() - 1)];
+
+                (offset(s.start), offset(s.end))
+            })
+            .collect();
+
+        // mixed LTR/RTL runs are reordered by pango (runs of the line are then in visual order)
+        // Inherit means that every paragraph has the direction of its first strong char
+        let (auto_dir, base_dir) = match text.direction {
+            Direction::Inherit => (true, pango::Direction::Ltr),
+            Direction::Ltr => (false, pango::Direction::Ltr),
+            Direction::Rtl => (false, pango::Direction::Rtl),
+        };
+        self.pango_context.set_base_dir(base_dir);
 
         let layout = pango::Layout::new(&self.pango_context);
-        layout.set_font_description(&description);
-        layout.set_wrap(WrapMode::Word);
-        layout.set_text(&text.text);
-        layout.set_alignment(text.align.clone().into());
+        layout.set_auto_dir(auto_dir);
+        layout.set_font_description(&font_description(text));
+        layout.set_wrap(text.word_break.clone().into());
+        layout.set_text(&collapsed);
+        layout.set_attributes(Some(&Self::attrs(text, &span_ranges)));
+        // lines are aligned by us (see layout_text) but justify needs to be done by pango
+        // (RTL lines are still aligned by pango to the right but that is subtracted later)
+        layout.set_alignment(Alignment::Left);
+        layout.set_justify(match text.align {
+            TextAlign::Justify => true,
+            _ => false,
+        });
+
+        (layout, span_ranges, offsets)
+    }
+
+    fn metrics(&self, text: &Text) -> TextMetrics {
+        let metrics = self
+            .pango_context
+            .get_metrics(Some(&font_description(text)), None)
+            .expect("couldn't get font metrics");
 
-        layout
+        TextMetrics {
+            ascent: from_scale(metrics.get_ascent()),
+            descent: from_scale(metrics.get_descent()),
+            // pango positions are above the baseline
+            underline_offset: -from_scale(metrics.get_underline_position()),
+            underline_thickness: from_scale(metrics.get_underline_thickness()),
+            strikethrough_offset: -from_scale(metrics.get_strikethrough_position()),
+            strikethrough_thickness: from_scale(metrics.get_strikethrough_thickness()),
+        }
+    }
+
+    // only what affects the layout, colors & underlines are up to the renderer
+    fn attrs(text: &Text, span_ranges: &[(usize, usize)]) -> AttrList {
+        let attrs = AttrList::new();
+
+        // whole text (attrs are unbounded by default)
+        if text.letter_spacing != 0. {
+            attrs.insert(Attribute::new_letter_spacing(to_scale(text.letter_spacing)).expect("letter spacing attr"));
+        }
+
+        if !text.font_features.is_empty() {
+            attrs.insert(Attribute::new_font_features(&features_string(&text.font_features)).expect("font features attr"));
+        }
+
+        for (span, (start, end)) in text.spans.iter().zip(span_ranges) {
+            let mut span_attrs = vec![];
+
+            if let Some(font_size) = span.font_size {
+                span_attrs.push(Attribute::new_size_absolute(to_scale(font_size)));
+            }
+
+            // renderer has only regular & bold faces so it has to be one of those
+            if let Some(font_weight) = span.font_weight {
+                span_attrs.push(Attribute::new_weight(if is_bold(font_weight) { Weight::Bold } else { Weight::Normal }));
+            }
+
+            if let Some(FontStyle::Italic) = span.font_style {
+                span_attrs.push(Attribute::new_style(Style::Italic));
+            }
+
+            for mut attr in span_attrs.into_iter().flatten() {
+                attr.set_start_index(*start as u32);
+                attr.set_end_index(*end as u32);
+                attrs.insert(attr);
+            }
+        }
+
+        attrs
     }
 }
 
 impl TextLayoutAlgo for PangoService {
     fn layout_text(&mut self, text: &Text, max_width: Option<f32>) -> LaidText {
-        let layout = self.get_layout(text);
+        let (layout, span_ranges, offsets) = self.get_layout(text);
+        let no_wrap = match text.white_space {
+            WhiteSpace::NoWrap | WhiteSpace::Pre => true,
+            _ => false,
+        };
+        let ellipsize = match text.ellipsize {
+            Ellipsize::None => false,
+            _ => true,
+        };
+
+        // unwrapped text is not limited, unless it should be ellipsized (at the max_width)
+        let max_width = if no_wrap && !ellipsize { None } else { max_width };
         layout.set_width(to_scale(max_width.unwrap_or(-1.)));
 
-        let lines = layout.get_line_count();
+        // with the default (-1) height, every paragraph is then ellipsized to one line
+        if no_wrap && ellipsize {
+            layout.set_ellipsize(text.ellipsize.clone().into());
+        }
+
+        // negative height would be the max number of lines but pango applies it per paragraph
+        // so we limit the height of the whole layout to the bottom of the last visible line instead
+        // (the ellipsis is then on that line, even if it's not the last one of its paragraph)
+        //
+        // it does nothing without ellipsize so we also need to skip the rest ourselves
+        // for wrapped text, ellipsize has to be set only here, otherwise pango would limit every paragraph to one line
+        if ellipsize && (text.number_of_lines != 0) && (layout.get_line_count() > text.number_of_lines as i32) {
+            let mut iter = layout.get_iter().expect("couldnt get LayoutIter");
+
+            for _ in 1..text.number_of_lines {
+                iter.next_line();
+            }
+
+            layout.set_height(iter.get_line_yrange().1);
+            layout.set_ellipsize(text.ellipsize.clone().into());
+        }
+
+        let line_count = layout.get_line_count();
+        let lines = match text.number_of_lines {
+            0 => line_count,
+            n => line_count.min(n as i32),
+        };
+        let truncated = layout.is_ellipsized() || (lines < line_count);
         let cap_height = from_scale(layout.get_line(0).map(|l| l.get_extents().0.height).unwrap_or(0));
         let baseline = cap_height + ((text.line_height - cap_height) / 2.);
         let mut layout_iter = layout.get_iter().expect("couldnt get LayoutIter");
+        let mut fonts: Vec<LaidFont> = vec![];
         let mut glyphs = vec![];
+        let mut line_starts = vec![];
+        let mut span_rects: Vec<(usize, Rect)> = vec![];
 
-        // start_x is important because of text-align (short text would be rendered at the side)
-        let start_x = layout.get_pixel_extents().0.x as f32;
+        // pango can align too but then the x is relative to max_width (or the widest line)
+        // which is not what we want if the text is then measured to be shorter
+        // so we align every line inside of the block ourselves and the renderer then aligns the block
+        //
+        // only the lines which are not skipped are measured
+        let line_widths: Vec<f32> = (0..lines)
+            .map(|i| layout.get_line(i).map(|l| from_scale(l.get_extents().1.width)).unwrap_or(0.))
+            .collect();
+        let width = line_widths.iter().cloned().fold(0., f32::max).ceil();
+        let line_offsets: Vec<f32> = line_widths.iter().map(|w| align_offset(&text.align, width, *w)).collect();
+        let line_rects = line_widths
+            .iter()
+            .zip(&line_offsets)
+            .enumerate()
+            .map(|(i, (w, x))| Rect(*x, i as f32 * text.line_height, *w, text.line_height))
+            .collect();
 
         // Ugly, but to my extent the only way to get `x`, `glyph_index` and `line_index`
         // together so we can do proper line-height text layout
         // I've tried many times and it's unlikely that there is a better way
         //
+        // Every line has one or more runs (one for each font/direction) and then "empty" run at the end
+        // Font can be different from what we've asked for (fallback) so we need to tell the renderer
+        // x is computed from glyph widths so that ligatures, ellipsis & justified spaces are right
+        //
         // BTW: Integrating pango was mistake (it would have been far easier to do text layout myself)
         // (so instead of fixing bugs in this we should rather focus on our own impl)
         for line_i in 0..lines {
-            if let Some(run) = layout_iter.get_run_readonly() {
-            unsafe {
+            let line_top = line_i as f32 * text.line_height;
+            // make x relative to the start of the line (RTL lines are aligned by pango)
+            let line_offset = line_offsets[line_i as usize] - from_scale(layout_iter.get_line_extents().1.x);
+            // runs are in visual order so the start is the lowest index (empty lines have only the "empty" run)
+            let mut line_start = layout_iter.get_index();
+
+            while let Some(run) = layout_iter.get_run_readonly() {
+                let mut x = from_scale(layout_iter.get_run_extents().1.x) + line_offset;
+                line_start = line_start.min(layout_iter.get_index());
+
+                unsafe {
                     let (_, run): (usize, &PangoGlyphItem) = std::mem::transmute(run);
+                    let item = &*run.item;
+                    let glyph_string = &*run.glyphs;
+                    let rtl = (item.analysis.level % 2) == 1;
+                    let font = run_font(item);
+                    let font = match fonts.iter().position(|f| *f == font) {
+                        Some(i) => i,
+                        None => {
+                            fonts.push(font);
+                            fonts.len() - 1
+                        }
+                    };
 
-                    for i in 0..(*run.glyphs).num_glyphs {
-                        let glyph_index = (*(*(*run).glyphs).glyphs.offset(i as isize)).glyph;
-                        let extents = layout_iter.get_char_extents();
+                    // cluster ends at the start of the next one (in logical order)
+                    let log_clusters: Vec<i32> =
+                        (0..(glyph_string.num_glyphs as isize)).map(|i| *glyph_string.log_clusters.offset(i)).collect();
+                    let mut cluster_starts = log_clusters.clone();
+                    cluster_starts.sort();
+                    cluster_starts.dedup();
+
+                    for i in 0..(glyph_string.num_glyphs as isize) {
+                        let info = &*glyph_string.glyphs.offset(i);
+                        let log_cluster = log_clusters[i as usize];
+                        let index = (item.offset + log_cluster) as usize;
+                        let cluster_end = match cluster_starts.binary_search(&log_cluster) {
+                            Ok(j) => cluster_starts.get(j + 1).cloned().unwrap_or(item.length),
+                            Err(_) => item.length,
+                        };
+                        let width = from_scale(info.geometry.width);
+                        let span = span_ranges.iter().rposition(|(start, end)| (*start <= index) && (index < *end));
 
                         glyphs.push(LaidGlyph {
-                            glyph_index,
-                            x: from_scale(extents.x) - start_x,
-                            y: (line_i as f32 * text.line_height) + baseline
+                            glyph_index: info.glyph,
+                            font,
+                            x: x + from_scale(info.geometry.x_offset),
+                            y: line_top + baseline + from_scale(info.geometry.y_offset),
+                            advance: width,
+                            line: line_i as usize,
+                            cluster: (char_offset(&offsets, index), char_offset(&offsets, (item.offset + cluster_end) as usize)),
+                            rtl,
+                            span,
                         });
 
-                        layout_iter.next_char();
-                    }
+                        if let Some(span) = span {
+                            extend_span_rects(&mut span_rects, span, Rect(x, line_top, width, text.line_height));
+                        }
 
-                    // somehow related to \n
-                    if layout_iter.get_run_readonly().is_none() {
-                        layout_iter.next_char();
+                        x += width;
                     }
                 }
-            } else {
-                // empty lines?
-                layout_iter.next_char();
+
+                layout_iter.next_run();
             }
+
+            line_starts.push(char_offset(&offsets, line_start.min(layout_iter.get_index()) as usize));
+
+            // skip the "empty" run at the end of the line
+            layout_iter.next_run();
         }
 
         debug!("glyphs {:?}", (&text.text, &glyphs));
 
         LaidText {
             lines,
-            width: layout.get_pixel_size().0 as f32,
+            width,
+            baseline,
+            metrics: self.metrics(text),
+            fonts,
             glyphs,
+            truncated,
+            line_rects,
+            line_starts,
+            span_rects,
         }
     }
 }
 
+// what pango really used (bold/italic can be also from the span)
+unsafe fn run_font(item: &PangoItem) -> LaidFont {
+    let description = pango_font_describe_with_absolute_size(item.analysis.font);
+    let family = CStr::from_ptr(pango_font_description_get_family(description)).to_string_lossy().into_owned();
+
+    let font = LaidFont {
+        family,
+        size: from_scale(pango_font_description_get_size(description)),
+        bold: is_bold(pango_font_description_get_weight(description) as u32),
+        italic: pango_font_description_get_style(description) != PANGO_STYLE_NORMAL,
+        file: font_file(item.analysis.font),
+    };
+
+    pango_font_description_free(description);
+
+    font
+}
+
+// pangocairo fonts are PangoFcFont on linux, the pattern is the first field after the parent
+// (there's no getter in the pango we use and pangofc is not in pango-sys)
+#[cfg(target_os = "linux")]
+unsafe fn font_file(font: *mut PangoFont) -> Option<(String, u32)> {
+    use std::os::raw::{c_char, c_int, c_void};
+
+    #[repr(C)]
+    struct PangoFcFont {
+        parent_instance: PangoFont,
+        font_pattern: *mut c_void,
+    }
+
+    #[link(name = "fontconfig")]
+    extern "C" {
+        fn FcPatternGetString(p: *mut c_void, object: *const c_char, n: c_int, s: *mut *mut u8) -> c_int;
+        fn FcPatternGetInteger(p: *mut c_void, object: *const c_char, n: c_int, i: *mut c_int) -> c_int;
+    }
+
+    // FcResultMatch
+    const MATCH: c_int = 0;
+
+    let pattern = (*(font as *mut PangoFcFont)).font_pattern;
+    let mut file = std::ptr::null_mut();
+    let mut index = 0;
+
+    if pattern.is_null() || (FcPatternGetString(pattern, b"file\0".as_ptr() as *const c_char, 0, &mut file) != MATCH) {
+        return None;
+    }
+
+    if FcPatternGetInteger(pattern, b"index\0".as_ptr() as *const c_char, 0, &mut index) != MATCH {
+        index = 0;
+    }
+
+    Some((CStr::from_ptr(file as *const c_char).to_string_lossy().into_owned(), index as u32))
+}
+
+// core text fonts, the renderer has to find it by the family
+#[cfg(not(target_os = "linux"))]
+unsafe fn font_file(_font: *mut PangoFont) -> Option<(String, u32)> {
+    None
+}
+
+// first char of the original text which is at the byte (of the collapsed text)
+fn char_offset(offsets: &[usize], byte: usize) -> usize {
+    let mut i = match offsets.binary_search(&byte) {
+        Ok(i) => i,
+        Err(i) => i,
+    };
+
+    while (i > 0) && (offsets[i - 1] == byte) {
+        i -= 1;
+    }
+
+    i
+}
+
+// glyphs of the same span (next to each other) are merged together
+fn extend_span_rects(span_rects: &mut Vec<(usize, Rect)>, span: usize, glyph_rect: Rect) {
+    if let Some((last_span, rect)) = span_rects.last_mut() {
+        if (*last_span == span) && (rect.1 == glyph_rect.1) && ((rect.0 + rect.2 - glyph_rect.0).abs() < 0.5) {
+            rect.2 = glyph_rect.0 + glyph_rect.2 - rect.0;
+            return;
+        }
+    }
+
+    span_rects.push((span, glyph_rect));
+}
+
+fn font_description(text: &Text) -> pango::FontDescription {
+    let mut description = pango::FontDescription::new();
+    description.set_family("Arial");
+    description.set_absolute_size(to_scale_f64(text.font_size));
+
+    if !text.font_variations.is_empty() {
+        description.set_variations(&variations_string(&text.font_variations));
+    }
+
+    description
+}
+
+// in the same format as CSS (and harfbuzz), ie. "tnum=1,liga=0"
+fn features_string(features: &[FontFeature]) -> String {
+    features.iter().map(|f| format!("{}={}", f.tag, f.value)).collect::<Vec<_>>().join(",")
+}
+
+fn variations_string(variations: &[FontVariation]) -> String {
+    variations.iter().map(|v| format!("{}={}", v.tag, v.value)).collect::<Vec<_>>().join(",")
+}
+
 // pango values are scaled
 fn from_scale(v: i32) -> f32 {
     (v as f32) / (pango::SCALE as f32)
@@ -105,12 +403,24 @@ fn to_scale_f64(v: f32) -> f64 {
     (v as f64) * (pango::SCALE as f64)
 }
 
-impl Into<Alignment> for TextAlign {
-    fn into(self) -> Alignment {
+impl Into<EllipsizeMode> for Ellipsize {
+    fn into(self) -> EllipsizeMode {
+        match self {
+            Ellipsize::None => EllipsizeMode::None,
+            Ellipsize::Start => EllipsizeMode::Start,
+            Ellipsize::Middle => EllipsizeMode::Middle,
+            Ellipsize::End => EllipsizeMode::End,
+        }
+    }
+}
+
+impl Into<WrapMode> for WordBreak {
+    fn into(self) -> WrapMode {
         match self {
-            TextAlign::Left => Alignment::Left,
-            TextAlign::Center => Alignment::Center,
-            TextAlign::Right => Alignment::Right,
+            WordBreak::Normal => WrapMode::Word,
+            WordBreak::BreakAll => WrapMode::Char,
+            // words are broken only if they don't fit on the line
+            WordBreak::BreakWord => WrapMode::WordChar,
         }
     }
 }
@@ -118,7 +428,8 @@ impl Into<Alignment> for TextAlign {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::Color;
+    use crate::api::{Color, TextSpan};
+    use crate::generated::{GlyphBox, TextLayout};
 
     #[test]
     fn test() {
@@ -129,6 +440,17 @@ mod tests {
             font_size: 24.,
             line_height: 30.,
             align: TextAlign::Left,
+            number_of_lines: 0,
+            ellipsize: Ellipsize::None,
+            white_space: WhiteSpace::PreWrap,
+            word_break: WordBreak::Normal,
+            spans: vec![],
+            decoration: None,
+            shadows: vec![],
+            letter_spacing: 0.,
+            font_features: vec![],
+            font_variations: vec![],
+            direction: Direction::Inherit,
             text: "Hello world\n\nHello".into()
         };
 
@@ -150,4 +472,471 @@ mod tests {
         assert_ne!(res[15].x, 0.);
         assert_ne!(res[15].y, 0.);
     }
+
+    fn text(align: TextAlign, text: &str) -> Text {
+        Text {
+            color: Color(0, 0, 0, 1),
+            font_size: 24.,
+            line_height: 30.,
+            align,
+            number_of_lines: 0,
+            ellipsize: Ellipsize::None,
+            white_space: WhiteSpace::PreWrap,
+            word_break: WordBreak::Normal,
+            spans: vec![],
+            decoration: None,
+            shadows: vec![],
+            letter_spacing: 0.,
+            font_features: vec![],
+            font_variations: vec![],
+            direction: Direction::Inherit,
+            text: text.into()
+        }
+    }
+
+    #[test]
+    fn test_number_of_lines_across_paragraphs() {
+        let mut svc = PangoService::new();
+        let mut lines = |s: &str, n| {
+            let mut t = text(TextAlign::Left, s);
+            t.number_of_lines = n;
+            t.ellipsize = Ellipsize::End;
+            svc.layout_text(&t, Some(100.))
+        };
+        let last_glyph = |res: &LaidText| res.glyphs.iter().filter(|g| g.line as i32 == res.lines - 1).last().unwrap().glyph_index;
+
+        // one paragraph is ellipsized by pango itself
+        let single = lines("Hello world foo bar baz", 1);
+        assert_eq!(single.lines, 1);
+        assert!(single.truncated);
+
+        // the cut line is not the last one of its paragraph but it still has to end with the ellipsis
+        let multi = lines("Hello\nHello world foo bar baz\nbaz", 2);
+        assert_eq!(multi.lines, 2);
+        assert!(multi.truncated);
+        assert_eq!(last_glyph(&multi), last_glyph(&single));
+
+        // nothing to cut
+        let short = lines("Hello\nHi", 2);
+        assert_eq!(short.lines, 2);
+        assert!(!short.truncated);
+    }
+
+    #[test]
+    fn test_align() {
+        let mut svc = PangoService::new();
+        let s = "Hello world\nHi";
+
+        let left = svc.layout_text(&text(TextAlign::Left, s), None);
+        let center = svc.layout_text(&text(TextAlign::Center, s), None);
+        let right = svc.layout_text(&text(TextAlign::Right, s), None);
+
+        assert_eq!(left.glyphs.len(), 13);
+
+        // the widest line is the same
+        for i in 0..11 {
+            assert_eq!(left.glyphs[i].x, center.glyphs[i].x);
+            assert_eq!(left.glyphs[i].x, right.glyphs[i].x);
+        }
+
+        // "Hi" is moved, every line separately
+        assert_eq!(left.glyphs[11].x, 0.);
+        assert!(right.glyphs[11].x > 0.);
+        assert!((center.glyphs[11].x - right.glyphs[11].x / 2.).abs() < 0.01);
+
+        // but not stretched
+        assert_eq!(
+            right.glyphs[12].x - right.glyphs[11].x,
+            left.glyphs[12].x - left.glyphs[11].x
+        );
+    }
+
+    #[test]
+    fn test_justify() {
+        let mut svc = PangoService::new();
+        let s = "aaa bbb ccc ddd eee fff ggg";
+
+        let left = svc.layout_text(&text(TextAlign::Left, s), Some(150.));
+        let justify = svc.layout_text(&text(TextAlign::Justify, s), Some(150.));
+
+        assert!(left.lines > 1);
+        assert_eq!(left.glyphs.len(), justify.glyphs.len());
+
+        let first_line_end = left.glyphs.iter().filter(|g| g.y == left.glyphs[0].y).count() - 1;
+
+        // first line is stretched
+        assert_eq!(justify.glyphs[0].x, 0.);
+        assert!(justify.glyphs[first_line_end].x > left.glyphs[first_line_end].x);
+
+        // last line is not
+        let last = left.glyphs.len() - 1;
+        assert_eq!(justify.glyphs[last].x, left.glyphs[last].x);
+    }
+
+    #[test]
+    fn test_number_of_lines() {
+        let mut svc = PangoService::new();
+        let s = "Hello world hello world hello world";
+
+        let full = svc.layout_text(&text(TextAlign::Left, s), Some(100.));
+        assert!(full.lines > 2);
+        assert!(!full.truncated);
+
+        let mut clamped = text(TextAlign::Left, s);
+        clamped.number_of_lines = 2;
+        let clamped = svc.layout_text(&clamped, Some(100.));
+        assert_eq!(clamped.lines, 2);
+        assert!(clamped.truncated);
+        assert!(clamped.glyphs.len() < full.glyphs.len());
+
+        let mut ellipsized = text(TextAlign::Left, s);
+        ellipsized.number_of_lines = 1;
+        ellipsized.ellipsize = Ellipsize::End;
+        let ellipsized = svc.layout_text(&ellipsized, Some(100.));
+        assert_eq!(ellipsized.lines, 1);
+        assert!(ellipsized.truncated);
+        assert!(ellipsized.width <= 100.);
+    }
+
+    #[test]
+    fn test_white_space() {
+        let mut svc = PangoService::new();
+        let s = "  Hello   \n  world ";
+
+        let mut normal = text(TextAlign::Left, s);
+        normal.white_space = WhiteSpace::Normal;
+        let normal = svc.layout_text(&normal, None);
+        assert_eq!(normal.lines, 1);
+        assert_eq!(normal.glyphs.len(), "Hello world".len());
+
+        let mut pre_line = text(TextAlign::Left, s);
+        pre_line.white_space = WhiteSpace::PreLine;
+        let pre_line = svc.layout_text(&pre_line, None);
+        assert_eq!(pre_line.lines, 2);
+        assert_eq!(pre_line.glyphs.len(), "Helloworld".len());
+        assert_eq!(pre_line.glyphs[5].x, 0.);
+
+        // not wrapped, nor collapsed
+        let mut pre = text(TextAlign::Left, s);
+        pre.white_space = WhiteSpace::Pre;
+        let pre = svc.layout_text(&pre, Some(10.));
+        assert_eq!(pre.lines, 2);
+        assert_eq!(pre.glyphs.len(), s.len() - 1);
+        assert_ne!(pre.glyphs[2].x, 0.);
+
+        let mut no_wrap = text(TextAlign::Left, "Hello world hello world");
+        no_wrap.white_space = WhiteSpace::NoWrap;
+        let no_wrap = svc.layout_text(&no_wrap, Some(50.));
+        assert_eq!(no_wrap.lines, 1);
+        assert!(no_wrap.width > 50.);
+    }
+
+    #[test]
+    fn test_no_wrap_ellipsize() {
+        let mut svc = PangoService::new();
+        let mut t = text(TextAlign::Left, "Hello world hello world");
+        t.white_space = WhiteSpace::NoWrap;
+        t.ellipsize = Ellipsize::End;
+
+        let res = svc.layout_text(&t, Some(50.));
+        assert_eq!(res.lines, 1);
+        assert!(res.truncated);
+        assert!(res.width <= 50.);
+
+        // still one line per paragraph
+        t.white_space = WhiteSpace::Pre;
+        t.text = "Hello world hello world\nHi".into();
+
+        let res = svc.layout_text(&t, Some(50.));
+        assert_eq!(res.lines, 2);
+        assert!(res.truncated);
+
+        // nothing to cut
+        let res = svc.layout_text(&t, None);
+        assert_eq!(res.lines, 2);
+        assert!(!res.truncated);
+    }
+
+    #[test]
+    fn test_word_break() {
+        let mut svc = PangoService::new();
+        let s = "https://example.com/some/very/long/url";
+
+        let normal = svc.layout_text(&text(TextAlign::Left, s), Some(100.));
+        assert_eq!(normal.lines, 1);
+        assert!(normal.width > 100.);
+
+        for word_break in &[WordBreak::BreakAll, WordBreak::BreakWord] {
+            let mut broken = text(TextAlign::Left, s);
+            broken.word_break = word_break.clone();
+            let broken = svc.layout_text(&broken, Some(100.));
+
+            assert!(broken.lines > 1);
+            assert!(broken.width <= 100.);
+            assert_eq!(broken.glyphs.len(), normal.glyphs.len());
+        }
+
+        // break-word only breaks words which don't fit
+        let mut break_all = text(TextAlign::Left, "Hello world");
+        break_all.word_break = WordBreak::BreakAll;
+        let mut break_word = break_all.clone();
+        break_word.word_break = WordBreak::BreakWord;
+
+        let break_all = svc.layout_text(&break_all, Some(100.));
+        let break_word = svc.layout_text(&break_word, Some(100.));
+        assert_eq!(break_word.glyphs[6].x, 0.);
+        assert_ne!(break_all.glyphs[6].x, 0.);
+    }
+
+    #[test]
+    fn test_spans() {
+        let mut svc = PangoService::new();
+        let s = "Hello bold world";
+
+        let plain = svc.layout_text(&text(TextAlign::Left, s), None);
+        let mut bold = text(TextAlign::Left, s);
+        bold.spans.push(TextSpan {
+            start: 6,
+            end: 10,
+            color: Some(Color(255, 0, 0, 255)),
+            font_size: None,
+            font_weight: Some(700),
+            font_style: None,
+            underline: false,
+            link: Some(1),
+        });
+        let bold = svc.layout_text(&bold, None);
+
+        // still one line & the same glyphs
+        assert_eq!(bold.lines, 1);
+        assert_eq!(bold.glyphs.len(), plain.glyphs.len());
+
+        let spans: Vec<Option<usize>> = bold.glyphs.iter().map(|g| g.span).collect();
+        assert_eq!(&spans[5..11], &[None, Some(0), Some(0), Some(0), Some(0), None]);
+
+        // bold is wider
+        assert_eq!(bold.glyphs[6].x, plain.glyphs[6].x);
+        assert!(bold.glyphs[10].x > plain.glyphs[10].x);
+
+        // one rect for the whole span
+        assert_eq!(bold.span_rects.len(), 1);
+        let (span, Rect(x, y, w, h)) = bold.span_rects[0].clone();
+        assert_eq!((span, x, y, h), (0, bold.glyphs[6].x, 0., 30.));
+        assert!((x + w - bold.glyphs[10].x).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut svc = PangoService::new();
+        let res = svc.layout_text(&text(TextAlign::Center, "Hello world\nHi"), None);
+        let m = &res.metrics;
+
+        // underline is below & strikethrough above the baseline
+        assert!(m.underline_offset > 0.);
+        assert!(m.strikethrough_offset < 0.);
+        assert!(m.ascent > -m.strikethrough_offset);
+        assert!(m.underline_thickness > 0.);
+
+        // line rects are aligned too
+        assert_eq!(res.line_rects.len(), 2);
+        assert_eq!(res.line_rects[0].0, 0.);
+        assert_eq!(res.line_rects[1].0, res.glyphs[11].x);
+        assert_eq!(res.line_rects[1].1, 30.);
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let mut svc = PangoService::new();
+
+        let normal = svc.layout_text(&text(TextAlign::Left, "Hello"), None);
+        let mut spaced = text(TextAlign::Left, "Hello");
+        spaced.letter_spacing = 2.;
+        let spaced = svc.layout_text(&spaced, None);
+
+        // every glyph is moved by the spacing of all the previous ones
+        // (pango puts half of the spacing on each side so it's not exact)
+        for i in 0..5 {
+            assert!((spaced.glyphs[i].x - normal.glyphs[i].x - (i as f32) * 2.).abs() <= 1.);
+        }
+
+        assert!(spaced.width > normal.width);
+    }
+
+    #[test]
+    fn test_font_features() {
+        let features = vec![
+            FontFeature { tag: "tnum".into(), value: 1 },
+            FontFeature { tag: "liga".into(), value: 0 },
+        ];
+        assert_eq!(features_string(&features), "tnum=1,liga=0");
+
+        let variations = vec![FontVariation { tag: "wght".into(), value: 650. }];
+        assert_eq!(variations_string(&variations), "wght=650");
+
+        // tabular digits have the same width
+        let mut svc = PangoService::new();
+        let mut tnum = text(TextAlign::Left, "1111\n8888");
+        tnum.font_features = features;
+        let tnum = svc.layout_text(&tnum, None);
+        assert_eq!(tnum.line_rects[0].2, tnum.line_rects[1].2);
+    }
+
+    #[test]
+    fn test_fonts() {
+        let mut svc = PangoService::new();
+
+        let plain = svc.layout_text(&text(TextAlign::Left, "Hello world"), None);
+        assert_eq!(plain.fonts.len(), 1);
+        assert_eq!(plain.fonts[0].size, 24.);
+        assert!(!plain.fonts[0].bold);
+        assert!(plain.glyphs.iter().all(|g| g.font == 0));
+
+        let mut bold = text(TextAlign::Left, "Hello world");
+        bold.spans.push(TextSpan {
+            start: 6,
+            end: 11,
+            color: None,
+            font_size: Some(30.),
+            font_weight: Some(700),
+            font_style: None,
+            underline: false,
+            link: None,
+        });
+        let bold = svc.layout_text(&bold, None);
+        assert_eq!(bold.fonts.len(), 2);
+        assert_eq!(bold.fonts[1], LaidFont { family: bold.fonts[0].family.clone(), size: 30., bold: true, italic: false, file: bold.fonts[1].file.clone() });
+
+        // so that the renderer loads exactly the same file
+        #[cfg(target_os = "linux")]
+        assert!(bold.fonts.iter().all(|f| f.file.is_some()));
+        assert_eq!(bold.glyphs[0].font, 0);
+        assert_eq!(bold.glyphs[6].font, 1);
+
+        // chars which are not in the font are still laid out (with some fallback font)
+        let fallback = svc.layout_text(&text(TextAlign::Left, "Hi 你好"), None);
+        assert_eq!(fallback.glyphs.len(), 5);
+        assert!(fallback.glyphs[3].font < fallback.fonts.len());
+    }
+
+    #[test]
+    fn test_direction() {
+        let mut svc = PangoService::new();
+        let span = TextSpan {
+            start: 5,
+            end: 10,
+            color: None,
+            font_size: None,
+            font_weight: None,
+            font_style: None,
+            underline: false,
+            link: None,
+        };
+
+        let mut ltr = text(TextAlign::Left, "שלום world");
+        ltr.spans.push(span.clone());
+        ltr.direction = Direction::Ltr;
+        let ltr = svc.layout_text(&ltr, Some(500.));
+
+        let mut rtl = text(TextAlign::Left, "שלום world");
+        rtl.spans.push(span);
+        rtl.direction = Direction::Rtl;
+        let rtl = svc.layout_text(&rtl, Some(500.));
+
+        // the same glyphs, just reordered
+        assert_eq!(ltr.glyphs.len(), rtl.glyphs.len());
+        assert_eq!(ltr.width, rtl.width);
+
+        // latin word is on the right in LTR paragraph & on the left in RTL one
+        let word_x = |t: &LaidText| t.span_rects[0].1 .0;
+        assert!(word_x(&ltr) > 0.);
+        assert_eq!(word_x(&rtl), 0.);
+
+        // still aligned by us, not by pango (to the max_width)
+        assert!(rtl.glyphs.iter().all(|g| g.x < rtl.width));
+
+        // auto-detected from the first strong char
+        let auto = svc.layout_text(&text(TextAlign::Left, "שלום world"), Some(500.));
+        assert_eq!(auto.glyphs.iter().map(|g| g.x).collect::<Vec<_>>(), rtl.glyphs.iter().map(|g| g.x).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_offsets() {
+        let mut svc = PangoService::new();
+
+        let hello = svc.layout_text(&text(TextAlign::Left, "Hello world"), None);
+        assert_eq!(hello.line_starts, vec![0]);
+        assert_eq!(hello.offset_at(-10., 0.), 0);
+        assert_eq!(hello.offset_at(1000., 1000.), 11);
+        let Rect(x, y, w, h) = hello.caret_rect(0);
+        assert_eq!((x, y, w, h), (0., 0., 0., 30.));
+        assert_eq!(hello.caret_rect(11).0, hello.line_rects[0].2);
+
+        // every offset should be at its caret
+        for offset in 0..=11 {
+            let Rect(x, y, _, _) = hello.caret_rect(offset);
+            assert_eq!(hello.offset_at(x, y + 10.), offset);
+        }
+
+        // empty line has no glyphs
+        let lines = svc.layout_text(&text(TextAlign::Left, "Hello\n\nworld"), None);
+        assert_eq!(lines.line_starts, vec![0, 6, 7]);
+        let Rect(x, y, w, h) = lines.caret_rect(6);
+        assert_eq!((x, y, w, h), (0., 30., 0., 30.));
+        assert_eq!(lines.offset_at(50., 40.), 6);
+        assert_eq!(lines.offset_at(0., 70.), 7);
+
+        // one rect per (non-empty) line
+        let selection = lines.selection_rects(3, 9);
+        assert_eq!(selection.len(), 2);
+        assert_eq!((selection[0].0, selection[0].1), (lines.caret_rect(3).0, 0.));
+        assert_eq!((selection[1].0, selection[1].1), (0., 60.));
+
+        // offsets are in the original text
+        let mut collapsed = text(TextAlign::Left, "a   b");
+        collapsed.white_space = WhiteSpace::Normal;
+        let collapsed = svc.layout_text(&collapsed, None);
+        assert_eq!(collapsed.glyphs[1].cluster, (1, 4));
+        assert_eq!(collapsed.caret_rect(4).0, collapsed.glyphs[2].x);
+
+        // rtl starts on the right
+        let mut shalom = text(TextAlign::Left, "שלום");
+        shalom.direction = Direction::Rtl;
+        let shalom = svc.layout_text(&shalom, None);
+        assert!(shalom.glyphs.iter().all(|g| g.rtl));
+        assert!(shalom.caret_rect(0).0 > shalom.caret_rect(4).0);
+        assert_eq!(shalom.offset_at(-10., 0.), 4);
+    }
+
+    #[test]
+    fn test_text_layout() {
+        let mut svc = PangoService::new();
+
+        let layout: TextLayout = svc.layout_text(&text(TextAlign::Left, "Hello\nworld"), None).into();
+        assert_eq!(layout.height, 60.);
+        assert_eq!(layout.lines.len(), 2);
+        assert_eq!((layout.lines[1].start, layout.lines[1].baseline), (6, 30. + layout.baseline));
+        assert_eq!(layout.glyphs.len(), 10);
+
+        let GlyphBox { rect: Rect(x, y, _, height), line, start, end } = layout.glyphs[5].clone();
+        assert_eq!((x, y, height, line, start, end), (0., 30., 30., 1, 6, 7));
+    }
+
+    #[test]
+    fn test_collapse_white_space() {
+        let s = " a  b \n\n c\td ";
+
+        assert_eq!(collapse_white_space(s, &WhiteSpace::Normal).0, "a b c d");
+        assert_eq!(collapse_white_space(s, &WhiteSpace::NoWrap).0, "a b c d");
+        assert_eq!(collapse_white_space(s, &WhiteSpace::PreLine).0, "a b\n\nc d");
+        assert_eq!(collapse_white_space(s, &WhiteSpace::Pre).0, s);
+        assert_eq!(collapse_white_space(s, &WhiteSpace::PreWrap).0, s);
+
+        // every char (and the end) is mapped to the collapsed text
+        let (collapsed, offsets) = collapse_white_space("a  b", &WhiteSpace::Normal);
+        assert_eq!(collapsed, "a b");
+        assert_eq!(offsets, vec![0, 1, 1, 2, 3]);
+
+        let (_, offsets) = collapse_white_space("čau", &WhiteSpace::Pre);
+        assert_eq!(offsets, vec![0, 2, 3, 4]);
+    }
 }
@@ -1,17 +1,28 @@
 use crate::api::{
-    Scene, Window, WindowEvent,
+    Animation, Rect, Scene, Window, WindowEvent,
 };
+use crate::animation::Animator;
 use crate::render::{SceneRenderer, WebrenderRenderer};
 use crate::scene::ArrayScene;
 use gleam::gl::GlFns;
-use glfw::{Context, Window as GlfwWindow};
-use crate::generated::SurfaceId;
+use glfw::{Context, Key, Modifiers, Window as GlfwWindow};
+use crate::generated::{AnimatedProperty, AnimatedValue, SurfaceId};
+use crate::text::TextInputState;
+use std::time::Instant;
 
 pub struct AppWindow {
     glfw_window: GlfwWindow,
     scene: ArrayScene,
     renderer: WebrenderRenderer,
-    mouse_pos: (f32, f32)
+    animator: Animator,
+    mouse_pos: (f32, f32),
+    // text input which receives keys (and whether the mouse is selecting in it)
+    focused_input: Option<SurfaceId>,
+    selecting: bool,
+    // what was rendered last time (so we know when to re-render for the blink)
+    caret_visible: bool,
+    // `LayoutChanged` for observed surfaces (collected during render)
+    layout_events: Vec<WindowEvent>
 }
 
 impl AppWindow {
@@ -22,7 +33,12 @@ impl AppWindow {
             glfw_window,
             scene: ArrayScene::new(),
             renderer: WebrenderRenderer::new(gl, (0, 0)),
-            mouse_pos: (0., 0.)
+            animator: Animator::new(),
+            mouse_pos: (0., 0.),
+            focused_input: None,
+            selecting: false,
+            caret_visible: false,
+            layout_events: vec![]
         };
 
         window.update_sizes();
@@ -32,7 +48,21 @@ impl AppWindow {
 
     // TODO
     pub fn handle_event(&mut self, event: glfw::WindowEvent) -> Option<WindowEvent> {
-        // TODO: we don't need Option currently so maybe we can remove it in the future
+        // keys handled by the focused text input are not sent to js (only the resulting `TextChanged`)
+        match event {
+            glfw::WindowEvent::Key(key, _, action, modifiers) if action != glfw::Action::Release => {
+                if let Some(handled) = self.focused_input.and_then(|surface| self.text_input_key(surface, key, modifiers)) {
+                    return handled;
+                }
+            }
+            glfw::WindowEvent::Char(ch) => {
+                if let Some(surface) = self.focused_input {
+                    return self.edit_text_input(surface, |i| i.insert(&ch.to_string()));
+                }
+            }
+            _ => {}
+        }
+
         match event {
             event => Some(match event {
                 glfw::WindowEvent::CursorPos(x, y) => {
@@ -41,19 +71,33 @@ impl AppWindow {
 
                     self.mouse_pos = (x, y);
 
-                    WindowEvent::MouseMove { target: self.hit_test() }
+                    let (target, link, point) = self.hit_test();
+
+                    if self.selecting && (self.focused_input == Some(target)) {
+                        self.move_caret(target, point, true);
+                    }
+
+                    WindowEvent::MouseMove { target, link }
                 }
                 glfw::WindowEvent::Scroll(delta_x, delta_y) => {
                     self.scroll((delta_x as f32, delta_y as f32));
 
-                    WindowEvent::Scroll { target: self.hit_test() }
+                    WindowEvent::Scroll { target: self.hit_test().0 }
                 }
-                glfw::WindowEvent::MouseButton(_button, action, _modifiers) => {
-                    let target = self.hit_test();
+                glfw::WindowEvent::MouseButton(_button, action, modifiers) => {
+                    let (target, link, point) = self.hit_test();
 
                     match action {
-                        glfw::Action::Press => WindowEvent::MouseDown { target },
-                        glfw::Action::Release => WindowEvent::MouseUp { target },
+                        glfw::Action::Press => {
+                            self.focus_text_input(target, point, modifiers.contains(Modifiers::Shift));
+
+                            WindowEvent::MouseDown { target, link }
+                        }
+                        glfw::Action::Release => {
+                            self.selecting = false;
+
+                            WindowEvent::MouseUp { target, link }
+                        }
                         _ => unreachable!("mouse should not repeat"),
                     }
                 },
@@ -76,7 +120,199 @@ impl AppWindow {
         }
     }
 
-    fn hit_test(&self) -> SurfaceId {
+    pub fn take_layout_events(&mut self) -> Vec<WindowEvent> {
+        std::mem::replace(&mut self.layout_events, vec![])
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animator.is_running()
+    }
+
+    /// Advance running animations, set new values to the scene & render them
+    ///
+    /// opacity, transform & scroll offset can be updated without rebuilding the
+    /// display list (unless they weren't in the last one), anything else needs full render
+    pub fn tick(&mut self) -> Vec<WindowEvent> {
+        let (values, finished) = self.animator.tick(Instant::now());
+
+        let mut opacities = vec![];
+        let mut transforms = vec![];
+        let mut scroll_offsets = vec![];
+        let mut needs_render = false;
+
+        // so that they stay bound (even when they reach 1)
+        self.renderer.set_animated_opacities(self.animator.animated_surfaces(AnimatedProperty::Opacity));
+
+        for (surface, value) in values {
+            match value {
+                AnimatedValue::Opacity(opacity) => {
+                    self.scene.set_opacity(surface, opacity);
+                    opacities.push((surface, opacity));
+                }
+                AnimatedValue::Transform(transform) => {
+                    self.scene.set_transform(surface, Some(transform.clone()));
+                    transforms.push((surface, transform));
+                }
+                AnimatedValue::BackgroundColor(color) => {
+                    self.scene.set_background_color(surface, Some(color));
+                    needs_render = true;
+                }
+                AnimatedValue::ScrollOffset(offset) => scroll_offsets.push((surface, offset)),
+            }
+        }
+
+        // one frame (and one swap) per tick, either cheap update or full render
+        // (which has all the values in the scene already, scroll offsets are kept by the renderer)
+        if needs_render {
+            self.renderer.queue_scroll_offsets(&scroll_offsets);
+        } else if !opacities.is_empty() || !transforms.is_empty() || !scroll_offsets.is_empty() {
+            self.glfw_window.make_current();
+
+            match self.renderer.update_properties(&opacities, &transforms, &scroll_offsets) {
+                true => self.glfw_window.swap_buffers(),
+                false => needs_render = true,
+            }
+        }
+
+        if needs_render {
+            self.render();
+        }
+
+        if !scroll_offsets.is_empty() {
+            self.scene.set_scroll_positions(self.renderer.scroll_positions());
+        }
+
+        finished.iter().map(|a| a.end_event()).collect()
+    }
+
+    /// Re-render if the caret of the focused text input should blink
+    pub fn update_caret(&mut self) {
+        let visible = match self.focused_input.and_then(|surface| self.scene.text_input(surface)) {
+            Some(text_input) => text_input.caret_visible(Instant::now()),
+            None => false,
+        };
+
+        if visible != self.caret_visible {
+            self.caret_visible = visible;
+            self.render();
+        }
+    }
+
+    // clicking on a text input focuses it & places the caret, clicking anywhere else blurs it
+    fn focus_text_input(&mut self, target: SurfaceId, point: (f32, f32), select: bool) {
+        if self.focused_input == Some(target) {
+            self.selecting = true;
+
+            return self.move_caret(target, point, select);
+        }
+
+        if let Some(prev) = self.focused_input.take() {
+            self.scene.edit_text_input(prev, |i| i.blur());
+            self.render();
+        }
+
+        if self.scene.edit_text_input(target, |i| i.focus()).is_some() {
+            self.focused_input = Some(target);
+            self.selecting = true;
+            self.move_caret(target, point, false);
+        }
+    }
+
+    fn move_caret(&mut self, surface: SurfaceId, (x, y): (f32, f32), select: bool) {
+        let offset = match self.scene.text_offset_at(surface, x, y) {
+            Some(offset) => offset,
+            None => return,
+        };
+
+        self.scene.edit_text_input(surface, |i| i.move_to(offset, select));
+        self.render();
+    }
+
+    // editing & navigation, None if the key is not handled (and should be sent to js)
+    fn text_input_key(&mut self, surface: SurfaceId, key: Key, modifiers: Modifiers) -> Option<Option<WindowEvent>> {
+        // cmd on mac
+        let ctrl = modifiers.intersects(Modifiers::Control | Modifiers::Super);
+        let shift = modifiers.contains(Modifiers::Shift);
+        let text_input = self.scene.text_input(surface)?;
+        let caret = text_input.caret();
+        let multiline = text_input.is_multiline();
+
+        Some(match key {
+            Key::Backspace => self.edit_text_input(surface, |i| i.delete_backward(ctrl)),
+            Key::Delete => self.edit_text_input(surface, |i| i.delete_forward(ctrl)),
+            Key::Left => self.edit_text_input(surface, |i| i.move_left(ctrl, shift)),
+            Key::Right => self.edit_text_input(surface, |i| i.move_right(ctrl, shift)),
+            Key::Home | Key::End => {
+                let line_starts = self.scene.text_layout(surface).map(|l| l.line_starts).unwrap_or_default();
+                let line = line_starts.iter().rposition(|s| *s <= caret).unwrap_or(0);
+                let offset = match key {
+                    Key::Home => line_starts.get(line).cloned().unwrap_or(0),
+                    // before the newline/space where the next line starts (it's clamped for the last one)
+                    _ => line_starts.get(line + 1).map(|s| s - 1).unwrap_or(usize::MAX),
+                };
+
+                self.edit_text_input(surface, |i| i.move_to(offset, shift))
+            }
+            Key::Up | Key::Down if multiline => {
+                // handled even if there's no (laid out) text
+                let Rect(x, y, _, height) = match self.scene.caret_rect(surface, caret) {
+                    Some(rect) => rect,
+                    None => return Some(None),
+                };
+                let y = if key == Key::Up { y - height / 2. } else { y + height * 1.5 };
+
+                match self.scene.text_offset_at(surface, x, y) {
+                    Some(offset) => self.edit_text_input(surface, |i| i.move_to(offset, shift)),
+                    None => None,
+                }
+            }
+            Key::Enter | Key::KpEnter if multiline => self.edit_text_input(surface, |i| i.insert("\n")),
+            Key::A if ctrl => self.edit_text_input(surface, TextInputState::select_all),
+            Key::Z if ctrl && shift => self.edit_text_input(surface, TextInputState::redo),
+            Key::Z if ctrl => self.edit_text_input(surface, TextInputState::undo),
+            Key::Y if ctrl => self.edit_text_input(surface, TextInputState::redo),
+            // secure inputs have no selected text so they are never copied
+            Key::C if ctrl => {
+                if let Some(selected) = text_input.selected_text() {
+                    self.glfw_window.set_clipboard_string(&selected);
+                }
+
+                None
+            }
+            Key::X if ctrl => {
+                let mut cut = None;
+                let event = self.edit_text_input(surface, |i| cut = i.cut());
+
+                if let Some(text) = cut {
+                    self.glfw_window.set_clipboard_string(&text);
+                }
+
+                event
+            }
+            Key::V if ctrl => {
+                let text = self.glfw_window.get_clipboard_string();
+
+                self.edit_text_input(surface, |i| i.insert(&text))
+            }
+            _ => return None,
+        })
+    }
+
+    // apply the edit & re-render, js gets the new value if it has changed
+    fn edit_text_input<R>(&mut self, surface: SurfaceId, f: impl FnOnce(&mut TextInputState) -> R) -> Option<WindowEvent> {
+        let prev = self.scene.text_input(surface)?.value().to_string();
+
+        self.scene.edit_text_input(surface, f);
+        self.render();
+
+        match self.scene.text_input(surface)?.value() {
+            value if value != prev => Some(WindowEvent::TextChanged { target: surface, value: value.to_string() }),
+            _ => None,
+        }
+    }
+
+    // surface, link (if there's a link under the cursor) & the point relative to the surface
+    fn hit_test(&self) -> (SurfaceId, Option<u32>, (f32, f32)) {
         let (x, y) = self.mouse_pos;
 
         self
@@ -85,13 +321,15 @@ impl AppWindow {
             // for any window event, there's always hit (root surface at least) because it's somewhere inside
             // we need to send some MouseMove event because of onMouseOut (prevTarget !== target)
             // TODO: should be a const or something
-            .unwrap_or(0)
+            .unwrap_or((0, None, (x, y)))
     }
 
     fn scroll(&mut self, delta: (f32, f32)) {
         self.glfw_window.make_current();
         self.renderer.scroll(self.mouse_pos, delta);
         self.glfw_window.swap_buffers();
+
+        self.scene.set_scroll_positions(self.renderer.scroll_positions());
     }
 
     fn update_sizes(&mut self) {
@@ -114,11 +352,18 @@ impl Window for AppWindow {
     fn render(&mut self) {
         self.scene.calculate_layout();
 
+        let changed = self.scene.changed_layouts();
+        self.layout_events.extend(changed.into_iter().map(|(target, rect)| WindowEvent::LayoutChanged { target, rect }));
+
         self.glfw_window.make_current();
         self.renderer.render(&self.scene);
         self.glfw_window.swap_buffers();
     }
 
+    fn animate(&mut self, animation: Animation) {
+        self.animator.add(animation);
+    }
+
     fn set_size(&mut self, width: i32, height: i32) {
         self.glfw_window.set_size(width, height);
         self.update_sizes();
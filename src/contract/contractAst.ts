@@ -23,6 +23,13 @@ const Rect = Tuple('Rect', [
   T.Scalar.F32
 ])
 
+// computed layout, relative to the parent & to the window
+// (absolute includes transforms & scroll offsets)
+const SurfaceLayout = Struct('SurfaceLayout', {
+  rect: T.RefTo(Rect),
+  absolute: T.RefTo(Rect)
+})
+
 const Dimensions = Tuple('Dimensions', [
   T.RefTo(Dimension),
   T.RefTo(Dimension),
@@ -62,13 +69,17 @@ const FlexWrap = Enum('FlexWrap', {
   variants: ['NoWrap', 'Wrap', 'WrapReverse']
 })
 
+// Inherit means LTR for the root
+const Direction = Enum('Direction', { variants: ['Inherit', 'Ltr', 'Rtl'] })
+
 const Flow = Struct('Flow', {
   flexDirection: T.RefTo(FlexDirection),
   flexWrap: T.RefTo(FlexWrap),
   alignContent: T.RefTo(FlexAlign),
   alignItems: T.RefTo(FlexAlign),
   alignSelf: T.RefTo(FlexAlign),
-  justifyContent: T.RefTo(JustifyContent)
+  justifyContent: T.RefTo(JustifyContent),
+  direction: T.RefTo(Direction)
 })
 
 const Flex = Struct('Flex', {
@@ -81,16 +92,101 @@ const Color = Tuple('Color', new Array(4).fill(T.Scalar.U8))
 
 const Vector2f = Tuple('Vector2f', [T.Scalar.F32, T.Scalar.F32])
 
-const BorderRadius = Tuple('BorderRadius', new Array(4).fill(T.Scalar.F32))
+// horizontal & vertical (elliptical), percents are relative to the surface size
+const CornerRadius = Tuple('CornerRadius', [
+  T.RefTo(Dimension),
+  T.RefTo(Dimension)
+])
+
+// top-left, top-right, bottom-left, bottom-right
+const BorderRadius = Tuple(
+  'BorderRadius',
+  new Array(4).fill(T.RefTo(CornerRadius))
+)
 
 const BoxShadow = Struct('BoxShadow', {
   color: T.RefTo(Color),
   offset: T.RefTo(Vector2f),
   blur: T.Scalar.F32,
-  spread: T.Scalar.F32
+  spread: T.Scalar.F32,
+  inset: T.Scalar.Bool
 })
 
-const BorderStyle = Enum('BorderStyle', { variants: ['None', 'Solid'] })
+// 2D affine matrix (a, b, c, d, e, f) like in CSS matrix()
+const Matrix = Tuple('Matrix', new Array(6).fill(T.Scalar.F32))
+
+// angles are in degrees
+const TransformOp = Union(
+  'TransformOp',
+  [
+    V.NewType('Matrix', T.RefTo(Matrix)),
+    V.NewType('Translate', T.RefTo(Vector2f)),
+    V.NewType('Scale', T.RefTo(Vector2f)),
+    V.NewType('Rotate', T.Scalar.F32),
+    V.NewType('Skew', T.RefTo(Vector2f))
+  ],
+  { tagAnnotation: false }
+)
+
+const TransformOrigin = Tuple('TransformOrigin', [
+  T.RefTo(Dimension),
+  T.RefTo(Dimension)
+])
+
+const Transform = Struct('Transform', {
+  ops: T.Vec(T.RefTo(TransformOp)),
+  origin: T.RefTo(TransformOrigin)
+})
+
+// offset is in 0..1 range
+const ColorStop = Struct('ColorStop', {
+  offset: T.Scalar.F32,
+  color: T.RefTo(Color)
+})
+
+// angle is in degrees, like in CSS (0 = to top, 90 = to right)
+const LinearGradient = Struct('LinearGradient', {
+  angle: T.Scalar.F32,
+  stops: T.Vec(T.RefTo(ColorStop))
+})
+
+// center & radius are relative to the surface size (0.5 = 50%)
+const RadialGradient = Struct('RadialGradient', {
+  center: T.RefTo(Vector2f),
+  radius: T.RefTo(Vector2f),
+  stops: T.Vec(T.RefTo(ColorStop))
+})
+
+// center is relative, angle (where it starts) is in degrees
+const ConicGradient = Struct('ConicGradient', {
+  center: T.RefTo(Vector2f),
+  angle: T.Scalar.F32,
+  stops: T.Vec(T.RefTo(ColorStop))
+})
+
+const Background = Union(
+  'Background',
+  [
+    V.NewType('LinearGradient', T.RefTo(LinearGradient)),
+    V.NewType('RadialGradient', T.RefTo(RadialGradient)),
+    V.NewType('ConicGradient', T.RefTo(ConicGradient))
+  ],
+  { tagAnnotation: false }
+)
+
+const BorderStyle = Enum('BorderStyle', {
+  variants: [
+    'None',
+    'Solid',
+    'Dashed',
+    'Dotted',
+    'Double',
+    'Groove',
+    'Ridge',
+    'Inset',
+    'Outset'
+  ]
+})
 
 const BorderSide = Struct('BorderSide', {
   width: T.Scalar.F32,
@@ -98,16 +194,108 @@ const BorderSide = Struct('BorderSide', {
   color: T.RefTo(Color)
 })
 
+const Image = Struct('Image', { url: T.Scalar.Str })
+
+const BorderImageSource = Union(
+  'BorderImageSource',
+  [
+    V.NewType('Image', T.RefTo(Image)),
+    V.NewType('LinearGradient', T.RefTo(LinearGradient)),
+    V.NewType('RadialGradient', T.RefTo(RadialGradient))
+  ],
+  { tagAnnotation: false }
+)
+
+// top, right, bottom, left (in pixels of the source)
+const BorderImageSlice = Tuple(
+  'BorderImageSlice',
+  new Array(4).fill(T.Scalar.U32)
+)
+
+const BorderImageRepeat = Enum('BorderImageRepeat', {
+  variants: ['Stretch', 'Repeat', 'Round', 'Space']
+})
+
+// replaces the sides (but their widths are still used)
+const BorderImage = Struct('BorderImage', {
+  source: T.RefTo(BorderImageSource),
+  slice: T.RefTo(BorderImageSlice),
+  fill: T.Scalar.Bool,
+  repeat: T.RefTo(BorderImageRepeat)
+})
+
 const Border = Struct('Border', {
   top: T.RefTo(BorderSide),
   right: T.RefTo(BorderSide),
   bottom: T.RefTo(BorderSide),
-  left: T.RefTo(BorderSide)
+  left: T.RefTo(BorderSide),
+  image: T.Option(T.RefTo(BorderImage))
 })
 
-const Image = Struct('Image', { url: T.Scalar.Str })
+// either inline svg source or a path to the .svg file
+const Svg = Union(
+  'Svg',
+  [V.NewType('Source', T.Scalar.Str), V.NewType('Path', T.Scalar.Str)],
+  { tagAnnotation: false }
+)
+
+const TextAlign = Enum('TextAlign', { variants: ['Left', 'Center', 'Right', 'Justify'] })
+
+// where to put the "…" if the text doesn't fit (in numberOfLines)
+const Ellipsize = Enum('Ellipsize', { variants: ['None', 'Start', 'Middle', 'End'] })
+
+// like in CSS
+const WhiteSpace = Enum('WhiteSpace', {
+  variants: ['Normal', 'NoWrap', 'Pre', 'PreWrap', 'PreLine']
+})
+const WordBreak = Enum('WordBreak', { variants: ['Normal', 'BreakAll', 'BreakWord'] })
+
+const FontStyle = Enum('FontStyle', { variants: ['Normal', 'Italic'] })
+
+// attributes for a range of the text (start & end are char offsets)
+// anything which is not set is inherited from the text
+const TextSpan = Struct('TextSpan', {
+  start: T.Scalar.U32,
+  end: T.Scalar.U32,
+  color: T.Option(T.RefTo(Color)),
+  fontSize: T.Option(T.Scalar.F32),
+  fontWeight: T.Option(T.Scalar.U32),
+  fontStyle: T.Option(T.RefTo(FontStyle)),
+  underline: T.Scalar.Bool,
+  // reported in mouse events if the span is under the cursor
+  link: T.Option(T.Scalar.U32)
+})
 
-const TextAlign = Enum('TextAlign', { variants: ['Left', 'Center', 'Right'] })
+const TextDecorationStyle = Enum('TextDecorationStyle', {
+  variants: ['Solid', 'Double', 'Dotted', 'Dashed', 'Wavy']
+})
+
+// lines are drawn for every line of the text (color defaults to the text color)
+const TextDecoration = Struct('TextDecoration', {
+  underline: T.Scalar.Bool,
+  lineThrough: T.Scalar.Bool,
+  overline: T.Scalar.Bool,
+  color: T.Option(T.RefTo(Color)),
+  style: T.RefTo(TextDecorationStyle)
+})
+
+const TextShadow = Struct('TextShadow', {
+  offset: T.RefTo(Vector2f),
+  blur: T.Scalar.F32,
+  color: T.RefTo(Color)
+})
+
+// opentype feature, ie. tnum=1 or liga=0
+const FontFeature = Struct('FontFeature', {
+  tag: T.Scalar.Str,
+  value: T.Scalar.U32
+})
+
+// variable font axis, ie. wght=650
+const FontVariation = Struct('FontVariation', {
+  tag: T.Scalar.Str,
+  value: T.Scalar.F32
+})
 
 // TODO: font family/query, weight, size
 const Text = Struct('Text', {
@@ -115,9 +303,61 @@ const Text = Struct('Text', {
   fontSize: T.Scalar.F32,
   lineHeight: T.Scalar.F32,
   align: T.RefTo(TextAlign),
+  // 0 means no limit
+  numberOfLines: T.Scalar.U32,
+  ellipsize: T.RefTo(Ellipsize),
+  whiteSpace: T.RefTo(WhiteSpace),
+  wordBreak: T.RefTo(WordBreak),
+  spans: T.Vec(T.RefTo(TextSpan)),
+  decoration: T.Option(T.RefTo(TextDecoration)),
+  shadows: T.Vec(T.RefTo(TextShadow)),
+  letterSpacing: T.Scalar.F32,
+  fontFeatures: T.Vec(T.RefTo(FontFeature)),
+  fontVariations: T.Vec(T.RefTo(FontVariation)),
+  // base direction of the paragraphs, Inherit means detect it from the first strong char
+  direction: T.RefTo(Direction),
   text: T.Scalar.Str
 })
 
+// native editing (caret, selection, undo, clipboard), the style is taken from the surface text
+// value is replaced only if it's different (so the caret stays where it was)
+const TextInput = Struct('TextInput', {
+  value: T.Scalar.Str,
+  placeholder: T.Scalar.Str,
+  placeholderColor: T.Option(T.RefTo(Color)),
+  secure: T.Scalar.Bool,
+  multiline: T.Scalar.Bool
+})
+
+// (aligned) line extents, where it starts & where its baseline is
+const TextLine = Struct('TextLine', {
+  rect: T.RefTo(Rect),
+  start: T.Scalar.USIZE,
+  baseline: T.Scalar.F32
+})
+
+// glyph advance (of the line height) & chars of the cluster it belongs to
+const GlyphBox = Struct('GlyphBox', {
+  rect: T.RefTo(Rect),
+  line: T.Scalar.USIZE,
+  start: T.Scalar.USIZE,
+  end: T.Scalar.USIZE
+})
+
+// laid out text, everything is relative to the text block & offsets are in chars
+const TextLayout = Struct('TextLayout', {
+  width: T.Scalar.F32,
+  height: T.Scalar.F32,
+  // from the top of the line
+  baseline: T.Scalar.F32,
+  ascent: T.Scalar.F32,
+  descent: T.Scalar.F32,
+  // some lines were skipped or ellipsized
+  truncated: T.Scalar.Bool,
+  lines: T.Vec(T.RefTo(TextLine)),
+  glyphs: T.Vec(T.RefTo(GlyphBox))
+})
+
 const Overflow = Enum('Overflow', {
   variants: [
     'Visible',
@@ -126,6 +366,39 @@ const Overflow = Enum('Overflow', {
   ]
 })
 
+const PositionType = Enum('PositionType', {
+  variants: [
+    'Relative',
+    'Absolute'
+  ]
+})
+
+// offsets which are not set should be Auto
+const Position = Struct('Position', {
+  kind: T.RefTo(PositionType),
+  top: T.RefTo(Dimension),
+  right: T.RefTo(Dimension),
+  bottom: T.RefTo(Dimension),
+  left: T.RefTo(Dimension)
+})
+
+// None takes no space (the whole subtree is skipped)
+const Display = Enum('Display', {
+  variants: [
+    'Flex',
+    'None'
+  ]
+})
+
+// Hidden keeps the layout but the surface is not painted (nor hit-tested),
+// children inherit it unless they have their own (None)
+const Visibility = Enum('Visibility', {
+  variants: [
+    'Visible',
+    'Hidden'
+  ]
+})
+
 const UpdateSceneMsg = Union(
   'UpdateSceneMsg',
   [
@@ -171,9 +444,10 @@ const UpdateSceneMsg = Union(
       surface: T.RefTo(SurfaceId),
       margin: T.RefTo(Dimensions)
     }),
-    V.Struct('SetBoxShadow', {
+    // first one is on top (like in CSS)
+    V.Struct('SetBoxShadows', {
       surface: T.RefTo(SurfaceId),
-      boxShadow: T.Option(T.RefTo(BoxShadow))
+      boxShadows: T.Vec(T.RefTo(BoxShadow))
     }),
     V.Struct('SetBackgroundColor', {
       surface: T.RefTo(SurfaceId),
@@ -190,6 +464,61 @@ const UpdateSceneMsg = Union(
     V.Struct('SetBorder', {
       surface: T.RefTo(SurfaceId),
       border: T.Option(T.RefTo(Border))
+    }),
+    V.Struct('SetSvg', {
+      surface: T.RefTo(SurfaceId),
+      svg: T.Option(T.RefTo(Svg))
+    }),
+    V.Struct('SetOpacity', {
+      surface: T.RefTo(SurfaceId),
+      opacity: T.Scalar.F32
+    }),
+    V.Struct('SetZIndex', {
+      surface: T.RefTo(SurfaceId),
+      zIndex: T.Scalar.I32
+    }),
+    V.Struct('SetTransform', {
+      surface: T.RefTo(SurfaceId),
+      transform: T.Option(T.RefTo(Transform))
+    }),
+    V.Struct('SetBackground', {
+      surface: T.RefTo(SurfaceId),
+      background: T.Option(T.RefTo(Background))
+    }),
+    V.Struct('SetTextInput', {
+      surface: T.RefTo(SurfaceId),
+      textInput: T.Option(T.RefTo(TextInput))
+    }),
+    // send LayoutChanged whenever the computed layout of the surface changes
+    V.Struct('ObserveLayout', {
+      surface: T.RefTo(SurfaceId),
+      observe: T.Scalar.Bool
+    }),
+    V.Struct('SetPosition', {
+      surface: T.RefTo(SurfaceId),
+      position: T.RefTo(Position)
+    }),
+    // Auto means no constraint
+    V.Struct('SetMinSize', {
+      surface: T.RefTo(SurfaceId),
+      size: T.RefTo(Size)
+    }),
+    V.Struct('SetMaxSize', {
+      surface: T.RefTo(SurfaceId),
+      size: T.RefTo(Size)
+    }),
+    // width / height
+    V.Struct('SetAspectRatio', {
+      surface: T.RefTo(SurfaceId),
+      aspectRatio: T.Option(T.Scalar.F32)
+    }),
+    V.Struct('SetDisplay', {
+      surface: T.RefTo(SurfaceId),
+      display: T.RefTo(Display)
+    }),
+    V.Struct('SetVisibility', {
+      surface: T.RefTo(SurfaceId),
+      visibility: T.Option(T.RefTo(Visibility))
     })
   ],
   { tagAnnotation: false }
@@ -197,18 +526,41 @@ const UpdateSceneMsg = Union(
 
 const WindowId = Alias('WindowId', T.Scalar.U16)
 
+const AnimatedProperty = Enum('AnimatedProperty', {
+  variants: ['Opacity', 'Transform', 'BackgroundColor', 'ScrollOffset']
+})
+
+// has to match the AnimatedProperty
+const AnimatedValue = Union(
+  'AnimatedValue',
+  [
+    V.NewType('Opacity', T.Scalar.F32),
+    V.NewType('Transform', T.RefTo(Transform)),
+    V.NewType('BackgroundColor', T.RefTo(Color)),
+    V.NewType('ScrollOffset', T.RefTo(Vector2f))
+  ],
+  { tagAnnotation: false }
+)
+
+const Easing = Enum('Easing', {
+  variants: ['Linear', 'EaseIn', 'EaseOut', 'EaseInOut']
+})
+
 // WIP
 const WindowEvent = Union(
   'WindowEvent',
   [
     V.Struct('MouseMove', {
-      target: T.Scalar.USIZE
+      target: T.Scalar.USIZE,
+      link: T.Option(T.Scalar.U32)
     }),
     V.Struct('MouseDown', {
-      target: T.Scalar.USIZE
+      target: T.Scalar.USIZE,
+      link: T.Option(T.Scalar.U32)
     }),
     V.Struct('MouseUp', {
-      target: T.Scalar.USIZE
+      target: T.Scalar.USIZE,
+      link: T.Option(T.Scalar.U32)
     }),
     V.Struct('Scroll', {
       target: T.Scalar.USIZE
@@ -225,7 +577,24 @@ const WindowEvent = Union(
     V.Unit('Close'),
 
     // TODO: temp
-    V.Unit('Unknown')
+    V.Unit('Unknown'),
+
+    V.Struct('AnimationEnd', {
+      target: T.Scalar.USIZE,
+      property: T.RefTo(AnimatedProperty)
+    }),
+
+    // value of the (focused) text input was edited
+    V.Struct('TextChanged', {
+      target: T.Scalar.USIZE,
+      value: T.Scalar.Str
+    }),
+
+    // observed surface has a new layout (relative to the parent)
+    V.Struct('LayoutChanged', {
+      target: T.Scalar.USIZE,
+      rect: T.RefTo(Rect)
+    })
   ],
   { tagAnnotation: false }
 )
@@ -246,7 +615,14 @@ const FfiResult = Union(
   [
     V.Unit('Nothing'),
     V.NewType('Events', T.Vec(T.RefTo(Event))),
-    V.NewType('WindowId', T.RefTo(WindowId))
+    V.NewType('WindowId', T.RefTo(WindowId)),
+    V.NewType('TextOffset', T.Scalar.U32),
+    V.NewType('Rect', T.RefTo(Rect)),
+    V.NewType('TextLayout', T.RefTo(TextLayout)),
+    V.NewType('Layouts', T.Vec(T.RefTo(SurfaceLayout))),
+    // result is bigger than the buffer, it has to be taken with
+    // `take_result` (into a buffer with at least the given length)
+    V.NewType('Pending', T.Scalar.U32)
   ],
   { tagAnnotation: false }
 )
@@ -259,6 +635,44 @@ const FfiMsg = Union(
     V.Struct('UpdateScene', {
       window: T.RefTo(WindowId),
       msgs: T.Vec(T.RefTo(UpdateSceneMsg))
+    }),
+    // duration & delay are in ms, 0 iterations means infinite
+    V.Struct('Animate', {
+      window: T.RefTo(WindowId),
+      surface: T.RefTo(SurfaceId),
+      property: T.RefTo(AnimatedProperty),
+      from: T.RefTo(AnimatedValue),
+      to: T.RefTo(AnimatedValue),
+      duration: T.Scalar.F32,
+      easing: T.RefTo(Easing),
+      delay: T.Scalar.F32,
+      iterations: T.Scalar.U32
+    }),
+    // char offset nearest to the point (relative to the surface)
+    V.Struct('TextOffsetAt', {
+      window: T.RefTo(WindowId),
+      surface: T.RefTo(SurfaceId),
+      x: T.Scalar.F32,
+      y: T.Scalar.F32
+    }),
+    // zero-width rect where the caret for the (char) offset should be drawn
+    V.Struct('CaretRect', {
+      window: T.RefTo(WindowId),
+      surface: T.RefTo(SurfaceId),
+      offset: T.Scalar.U32
+    }),
+    // lay out the text without any surface (maxWidth is for wrapping)
+    V.Struct('MeasureText', {
+      text: T.RefTo(Text),
+      maxWidth: T.Option(T.Scalar.F32)
+    }),
+    V.Struct('GetTextLayout', {
+      window: T.RefTo(WindowId),
+      surface: T.RefTo(SurfaceId)
+    }),
+    V.Struct('GetLayout', {
+      window: T.RefTo(WindowId),
+      surfaces: T.Vec(T.RefTo(SurfaceId))
     })
   ],
   { tagAnnotation: false }
@@ -272,25 +686,63 @@ export const exampleEntries: EntryT[] = [
   UpdateSceneMsg,
   WindowId,
   SurfaceId,
+  AnimatedProperty,
+  AnimatedValue,
+  Easing,
   Color,
   FlexDirection,
   FlexWrap,
   FlexAlign,
   JustifyContent,
+  Direction,
   Flow,
   Flex,
   Dimension,
   Overflow,
+  PositionType,
+  Position,
+  Display,
+  Visibility,
   Size,
   Rect,
+  SurfaceLayout,
   Dimensions,
   Vector2f,
+  CornerRadius,
   BorderRadius,
   BoxShadow,
+  Matrix,
+  TransformOp,
+  TransformOrigin,
+  Transform,
+  ColorStop,
+  LinearGradient,
+  RadialGradient,
+  ConicGradient,
+  Background,
   Image,
+  Svg,
   TextAlign,
+  Ellipsize,
+  WhiteSpace,
+  WordBreak,
+  FontStyle,
+  TextSpan,
+  TextDecorationStyle,
+  TextDecoration,
+  TextShadow,
+  FontFeature,
+  FontVariation,
   Text,
+  TextInput,
+  TextLine,
+  GlyphBox,
+  TextLayout,
   Border,
   BorderSide,
-  BorderStyle
+  BorderStyle,
+  BorderImage,
+  BorderImageSource,
+  BorderImageSlice,
+  BorderImageRepeat
 ]
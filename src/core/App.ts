@@ -1,4 +1,4 @@
-import { WindowId, FfiMsg, FfiResult, Event } from "./generated";
+import { WindowId, FfiMsg, FfiResult, Event, Text, TextLayout } from "./generated";
 import { Window } from "./Window";
 import * as ffi from './nativeApi'
 import { performance } from 'perf_hooks'
@@ -69,6 +69,15 @@ export class App {
   requestAnimationFrame(cb) {
     this.animationFrames.push(cb)
   }
+
+  // lay out the text without any window/surface (for text-fit, auto-sizing, ...)
+  measureText(text: Text, maxWidth?: number): TextLayout {
+    const res = this.ffi.send(FfiMsg.MeasureText({ text, maxWidth }))
+
+    if (res.tag === 'TextLayout') {
+      return res.value
+    }
+  }
 }
 
 // lazy created (and shared) App instance
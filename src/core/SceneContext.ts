@@ -1,4 +1,4 @@
-import { UpdateSceneMsg as U, FfiMsg } from './generated'
+import { UpdateSceneMsg as U, FfiMsg, Easing, Rect, TextLayout, SurfaceLayout } from './generated'
 import { send } from './nativeApi'
 
 /**
@@ -12,6 +12,7 @@ export class SceneContext {
   nextId = 1
   // TODO: consider ordering related things together (structural, layout, visual changes)
   sceneMsgs = []
+  animations = []
   parents = []
 
   constructor(private windowId) {}
@@ -41,6 +42,19 @@ export class SceneContext {
     this.sceneMsgs.push(U.SetSize({ surface, size }))
   }
 
+  // Auto means no constraint
+  setMinSize(surface, size) {
+    this.sceneMsgs.push(U.SetMinSize({ surface, size }))
+  }
+
+  setMaxSize(surface, size) {
+    this.sceneMsgs.push(U.SetMaxSize({ surface, size }))
+  }
+
+  setAspectRatio(surface, aspectRatio) {
+    this.sceneMsgs.push(U.SetAspectRatio({ surface, aspectRatio }))
+  }
+
   setOverflow(surface, overflow) {
     this.sceneMsgs.push(U.SetOverflow({ surface, overflow }))
   }
@@ -61,12 +75,36 @@ export class SceneContext {
     this.sceneMsgs.push(U.SetMargin({ surface, margin }))
   }
 
+  setPosition(surface, position) {
+    this.sceneMsgs.push(U.SetPosition({ surface, position }))
+  }
+
+  setDisplay(surface, display) {
+    this.sceneMsgs.push(U.SetDisplay({ surface, display }))
+  }
+
   setBorderRadius(surface, borderRadius) {
     this.sceneMsgs.push(U.SetBorderRadius({ surface, borderRadius }))
   }
 
-  setBoxShadow(surface, boxShadow) {
-    this.sceneMsgs.push(U.SetBoxShadow({ surface, boxShadow }))
+  setOpacity(surface, opacity) {
+    this.sceneMsgs.push(U.SetOpacity({ surface, opacity }))
+  }
+
+  setVisibility(surface, visibility) {
+    this.sceneMsgs.push(U.SetVisibility({ surface, visibility }))
+  }
+
+  setZIndex(surface, zIndex) {
+    this.sceneMsgs.push(U.SetZIndex({ surface, zIndex }))
+  }
+
+  setTransform(surface, transform) {
+    this.sceneMsgs.push(U.SetTransform({ surface, transform }))
+  }
+
+  setBoxShadows(surface, boxShadows) {
+    this.sceneMsgs.push(U.SetBoxShadows({ surface, boxShadows }))
   }
 
   setBackgroundColor(surface, color) {
@@ -78,29 +116,96 @@ export class SceneContext {
     )
   }
 
+  setBackground(surface, background) {
+    this.sceneMsgs.push(U.SetBackground({ surface, background }))
+  }
+
   setImage(surface, image) {
     this.sceneMsgs.push(U.SetImage({ surface, image }))
   }
 
+  setSvg(surface, svg) {
+    this.sceneMsgs.push(U.SetSvg({ surface, svg }))
+  }
+
   setText(surface, text) {
     this.sceneMsgs.push(U.SetText({ surface, text }))
   }
 
+  setTextInput(surface, textInput) {
+    this.sceneMsgs.push(U.SetTextInput({ surface, textInput }))
+  }
+
   setBorder(surface, border) {
     this.sceneMsgs.push(U.SetBorder({ surface, border }))
   }
 
+  // LayoutChanged will be sent (after render) whenever the layout changes
+  observeLayout(surface, observe: boolean) {
+    this.sceneMsgs.push(U.ObserveLayout({ surface, observe }))
+  }
+
+  // runs natively, AnimationEnd event is sent when it's done
+  // (animations are started after the scene is updated so it's fine to animate new surfaces)
+  animate(surface, property, from, to, { duration = 300, easing = Easing.EaseInOut, delay = 0, iterations = 1 } = {}) {
+    this.animations.push({ surface, property, from, to, duration, easing, delay, iterations })
+  }
+
+  // queries are sync so pending changes need to be flushed first (to get up-to-date layout)
+  // both are relative to the surface and offsets are in chars (code points)
+  // (undefined if the surface has no text or it's not laid out, ie. display: none)
+  textOffsetAt(surface, x, y): number {
+    this.flush()
+    const res = send(FfiMsg.TextOffsetAt({ window: this.windowId, surface, x, y }))
+
+    if (res.tag === 'TextOffset') {
+      return res.value
+    }
+  }
+
+  caretRect(surface, offset): Rect {
+    this.flush()
+    const res = send(FfiMsg.CaretRect({ window: this.windowId, surface, offset }))
+
+    if (res.tag === 'Rect') {
+      return res.value
+    }
+  }
+
+  // lines & glyph boxes (relative to the text block) of the surface text
+  getTextLayout(surface): TextLayout {
+    this.flush()
+    const res = send(FfiMsg.GetTextLayout({ window: this.windowId, surface }))
+
+    if (res.tag === 'TextLayout') {
+      return res.value
+    }
+  }
+
+  // computed layouts (both relative to the parent and to the window) in the same order
+  getLayout(surfaces: number[]): SurfaceLayout[] {
+    this.flush()
+    const res = send(FfiMsg.GetLayout({ window: this.windowId, surfaces }))
+
+    if (res.tag === 'Layouts') {
+      return res.value
+    }
+  }
+
   flush() {
-    if (this.sceneMsgs.length === 0) {
-      return
+    if (this.sceneMsgs.length !== 0) {
+      send(
+        FfiMsg.UpdateScene({
+          window: this.windowId,
+          msgs: this.sceneMsgs
+        })
+      )
+      this.sceneMsgs = []
     }
 
-    send(
-      FfiMsg.UpdateScene({
-        window: this.windowId,
-        msgs: this.sceneMsgs
-      })
-    )
-    this.sceneMsgs = []
+    for (const animation of this.animations) {
+      send(FfiMsg.Animate({ window: this.windowId, ...animation }))
+    }
+    this.animations = []
   }
 }
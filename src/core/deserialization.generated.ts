@@ -3,7 +3,26 @@ import {
   WindowId,
   UpdateSceneMsg,
   FfiMsg_UpdateScene,
+  SurfaceId,
+  AnimatedProperty,
+  AnimatedValue,
+  Transform,
+  Color,
+  Vector2f,
+  Easing,
+  FfiMsg_Animate,
+  FfiMsg_TextOffsetAt,
+  FfiMsg_CaretRect,
+  Text,
+  FfiMsg_MeasureText,
+  FfiMsg_GetTextLayout,
+  FfiMsg_GetLayout,
   FfiResult,
+  Rect,
+  TextLayout,
+  TextLine,
+  GlyphBox,
+  SurfaceLayout,
   Event,
   WindowEvent,
   Event_WindowEvent,
@@ -11,7 +30,9 @@ import {
   WindowEvent_MouseDown,
   WindowEvent_MouseUp,
   WindowEvent_Scroll,
-  SurfaceId,
+  WindowEvent_AnimationEnd,
+  WindowEvent_TextChanged,
+  WindowEvent_LayoutChanged,
   UpdateSceneMsg_AppendChild,
   UpdateSceneMsg_InsertBefore,
   UpdateSceneMsg_RemoveChild,
@@ -29,25 +50,64 @@ import {
   UpdateSceneMsg_SetPadding,
   UpdateSceneMsg_SetMargin,
   BoxShadow,
-  UpdateSceneMsg_SetBoxShadow,
-  Color,
+  UpdateSceneMsg_SetBoxShadows,
   UpdateSceneMsg_SetBackgroundColor,
   Image,
   UpdateSceneMsg_SetImage,
-  Text,
   UpdateSceneMsg_SetText,
   Border,
   UpdateSceneMsg_SetBorder,
+  Svg,
+  UpdateSceneMsg_SetSvg,
+  UpdateSceneMsg_SetOpacity,
+  UpdateSceneMsg_SetZIndex,
+  UpdateSceneMsg_SetTransform,
+  Background,
+  UpdateSceneMsg_SetBackground,
+  TextInput,
+  UpdateSceneMsg_SetTextInput,
+  UpdateSceneMsg_ObserveLayout,
+  Position,
+  UpdateSceneMsg_SetPosition,
+  UpdateSceneMsg_SetMinSize,
+  UpdateSceneMsg_SetMaxSize,
+  UpdateSceneMsg_SetAspectRatio,
+  Display,
+  UpdateSceneMsg_SetDisplay,
+  Visibility,
+  UpdateSceneMsg_SetVisibility,
   FlexDirection,
   FlexWrap,
   FlexAlign,
   JustifyContent,
+  Direction,
   Dimension,
-  Rect,
-  Vector2f,
+  PositionType,
   TextAlign,
+  Ellipsize,
+  WhiteSpace,
+  WordBreak,
+  FontStyle,
+  TextSpan,
+  TextDecorationStyle,
+  TextDecoration,
+  TextShadow,
+  FontFeature,
+  FontVariation,
   BorderSide,
-  BorderStyle
+  BorderStyle,
+  BorderImage,
+  BorderImageSource,
+  BorderImageSlice,
+  BorderImageRepeat,
+  Matrix,
+  TransformOp,
+  TransformOrigin,
+  CornerRadius,
+  LinearGradient,
+  RadialGradient,
+  ConicGradient,
+  ColorStop
 } from './generated'
 
 import {
@@ -60,6 +120,7 @@ import {
   read_u8,
   read_f32,
   read_str,
+  read_i32,
   Sink,
   Deserializer
 } from 'ts-binary'
@@ -69,11 +130,23 @@ const readVecUpdateSceneMsg = (sink: Sink): Array<UpdateSceneMsg> =>
 
 const readVecEvent = (sink: Sink): Array<Event> => read_seq(sink, readEvent)
 
+const readVecTextLine = (sink: Sink): Array<TextLine> =>
+  read_seq(sink, readTextLine)
+
+const readVecGlyphBox = (sink: Sink): Array<GlyphBox> =>
+  read_seq(sink, readGlyphBox)
+
+const readVecSurfaceId = (sink: Sink): Array<SurfaceId> =>
+  read_seq(sink, readSurfaceId)
+
+const readVecSurfaceLayout = (sink: Sink): Array<SurfaceLayout> =>
+  read_seq(sink, readSurfaceLayout)
+
 const readOptBorderRadius = (sink: Sink): (BorderRadius) | undefined =>
   read_opt(sink, readBorderRadius)
 
-const readOptBoxShadow = (sink: Sink): (BoxShadow) | undefined =>
-  read_opt(sink, readBoxShadow)
+const readVecBoxShadow = (sink: Sink): Array<BoxShadow> =>
+  read_seq(sink, readBoxShadow)
 
 const readOptColor = (sink: Sink): (Color) | undefined =>
   read_opt(sink, readColor)
@@ -86,6 +159,51 @@ const readOptText = (sink: Sink): (Text) | undefined => read_opt(sink, readText)
 const readOptBorder = (sink: Sink): (Border) | undefined =>
   read_opt(sink, readBorder)
 
+const readOptSvg = (sink: Sink): (Svg) | undefined => read_opt(sink, readSvg)
+
+const readOptTransform = (sink: Sink): (Transform) | undefined =>
+  read_opt(sink, readTransform)
+
+const readVecTransformOp = (sink: Sink): Array<TransformOp> =>
+  read_seq(sink, readTransformOp)
+
+const readOptBackground = (sink: Sink): (Background) | undefined =>
+  read_opt(sink, readBackground)
+
+const readOptTextInput = (sink: Sink): (TextInput) | undefined =>
+  read_opt(sink, readTextInput)
+
+const readOptVisibility = (sink: Sink): (Visibility) | undefined =>
+  read_opt(sink, readVisibility)
+
+const readVecColorStop = (sink: Sink): Array<ColorStop> =>
+  read_seq(sink, readColorStop)
+
+const readOptBorderImage = (sink: Sink): (BorderImage) | undefined =>
+  read_opt(sink, readBorderImage)
+
+const readOptU32 = (sink: Sink): (number) | undefined => read_opt(sink, read_u32)
+
+const readVecTextSpan = (sink: Sink): Array<TextSpan> =>
+  read_seq(sink, readTextSpan)
+
+const readOptF32 = (sink: Sink): (number) | undefined => read_opt(sink, read_f32)
+
+const readOptFontStyle = (sink: Sink): (FontStyle) | undefined =>
+  read_opt(sink, readFontStyle)
+
+const readOptTextDecoration = (sink: Sink): (TextDecoration) | undefined =>
+  read_opt(sink, readTextDecoration)
+
+const readVecTextShadow = (sink: Sink): Array<TextShadow> =>
+  read_seq(sink, readTextShadow)
+
+const readVecFontFeature = (sink: Sink): Array<FontFeature> =>
+  read_seq(sink, readFontFeature)
+
+const readVecFontVariation = (sink: Sink): Array<FontVariation> =>
+  read_seq(sink, readFontVariation)
+
 export const readFfiMsg = (sink: Sink): FfiMsg => {
   switch (read_u32(sink)) {
     case 0:
@@ -94,6 +212,18 @@ export const readFfiMsg = (sink: Sink): FfiMsg => {
       return FfiMsg.CreateWindow
     case 2:
       return FfiMsg.UpdateScene(readFfiMsg_UpdateScene(sink))
+    case 3:
+      return FfiMsg.Animate(readFfiMsg_Animate(sink))
+    case 4:
+      return FfiMsg.TextOffsetAt(readFfiMsg_TextOffsetAt(sink))
+    case 5:
+      return FfiMsg.CaretRect(readFfiMsg_CaretRect(sink))
+    case 6:
+      return FfiMsg.MeasureText(readFfiMsg_MeasureText(sink))
+    case 7:
+      return FfiMsg.GetTextLayout(readFfiMsg_GetTextLayout(sink))
+    case 8:
+      return FfiMsg.GetLayout(readFfiMsg_GetLayout(sink))
   }
   throw new Error('bad variant index for FfiMsg')
 }
@@ -104,6 +234,62 @@ const readFfiMsg_UpdateScene = (sink: Sink): FfiMsg_UpdateScene => {
   return { window, msgs }
 }
 
+const readFfiMsg_Animate = (sink: Sink): FfiMsg_Animate => {
+  const window = readWindowId(sink)
+  const surface = readSurfaceId(sink)
+  const property = readAnimatedProperty(sink)
+  const from = readAnimatedValue(sink)
+  const to = readAnimatedValue(sink)
+  const duration = read_f32(sink)
+  const easing = readEasing(sink)
+  const delay = read_f32(sink)
+  const iterations = read_u32(sink)
+  return {
+    window,
+    surface,
+    property,
+    from,
+    to,
+    duration,
+    easing,
+    delay,
+    iterations
+  }
+}
+
+const readFfiMsg_TextOffsetAt = (sink: Sink): FfiMsg_TextOffsetAt => {
+  const window = readWindowId(sink)
+  const surface = readSurfaceId(sink)
+  const x = read_f32(sink)
+  const y = read_f32(sink)
+  return { window, surface, x, y }
+}
+
+const readFfiMsg_CaretRect = (sink: Sink): FfiMsg_CaretRect => {
+  const window = readWindowId(sink)
+  const surface = readSurfaceId(sink)
+  const offset = read_u32(sink)
+  return { window, surface, offset }
+}
+
+const readFfiMsg_MeasureText = (sink: Sink): FfiMsg_MeasureText => {
+  const text = readText(sink)
+  const maxWidth = readOptF32(sink)
+  return { text, maxWidth }
+}
+
+const readFfiMsg_GetTextLayout = (sink: Sink): FfiMsg_GetTextLayout => {
+  const window = readWindowId(sink)
+  const surface = readSurfaceId(sink)
+  return { window, surface }
+}
+
+const readFfiMsg_GetLayout = (sink: Sink): FfiMsg_GetLayout => {
+  const window = readWindowId(sink)
+  const surfaces = readVecSurfaceId(sink)
+  return { window, surfaces }
+}
+
 export const readFfiResult = (sink: Sink): FfiResult => {
   switch (read_u32(sink)) {
     case 0:
@@ -112,6 +298,16 @@ export const readFfiResult = (sink: Sink): FfiResult => {
       return FfiResult.Events(readVecEvent(sink))
     case 2:
       return FfiResult.WindowId(readWindowId(sink))
+    case 3:
+      return FfiResult.TextOffset(read_u32(sink))
+    case 4:
+      return FfiResult.Rect(readRect(sink))
+    case 5:
+      return FfiResult.TextLayout(readTextLayout(sink))
+    case 6:
+      return FfiResult.Layouts(readVecSurfaceLayout(sink))
+    case 7:
+      return FfiResult.Pending(read_u32(sink))
   }
   throw new Error('bad variant index for FfiResult')
 }
@@ -156,23 +352,32 @@ export const readWindowEvent = (sink: Sink): WindowEvent => {
       return WindowEvent.Close
     case 11:
       return WindowEvent.Unknown
+    case 12:
+      return WindowEvent.AnimationEnd(readWindowEvent_AnimationEnd(sink))
+    case 13:
+      return WindowEvent.TextChanged(readWindowEvent_TextChanged(sink))
+    case 14:
+      return WindowEvent.LayoutChanged(readWindowEvent_LayoutChanged(sink))
   }
   throw new Error('bad variant index for WindowEvent')
 }
 
 const readWindowEvent_MouseMove = (sink: Sink): WindowEvent_MouseMove => {
   const target = read_u64(sink)
-  return { target }
+  const link = readOptU32(sink)
+  return { target, link }
 }
 
 const readWindowEvent_MouseDown = (sink: Sink): WindowEvent_MouseDown => {
   const target = read_u64(sink)
-  return { target }
+  const link = readOptU32(sink)
+  return { target, link }
 }
 
 const readWindowEvent_MouseUp = (sink: Sink): WindowEvent_MouseUp => {
   const target = read_u64(sink)
-  return { target }
+  const link = readOptU32(sink)
+  return { target, link }
 }
 
 const readWindowEvent_Scroll = (sink: Sink): WindowEvent_Scroll => {
@@ -180,6 +385,28 @@ const readWindowEvent_Scroll = (sink: Sink): WindowEvent_Scroll => {
   return { target }
 }
 
+const readWindowEvent_AnimationEnd = (
+  sink: Sink
+): WindowEvent_AnimationEnd => {
+  const target = read_u64(sink)
+  const property = readAnimatedProperty(sink)
+  return { target, property }
+}
+
+const readWindowEvent_TextChanged = (sink: Sink): WindowEvent_TextChanged => {
+  const target = read_u64(sink)
+  const value = read_str(sink)
+  return { target, value }
+}
+
+const readWindowEvent_LayoutChanged = (
+  sink: Sink
+): WindowEvent_LayoutChanged => {
+  const target = read_u64(sink)
+  const rect = readRect(sink)
+  return { target, rect }
+}
+
 export const readUpdateSceneMsg = (sink: Sink): UpdateSceneMsg => {
   switch (read_u32(sink)) {
     case 0:
@@ -207,7 +434,7 @@ export const readUpdateSceneMsg = (sink: Sink): UpdateSceneMsg => {
     case 10:
       return UpdateSceneMsg.SetMargin(readUpdateSceneMsg_SetMargin(sink))
     case 11:
-      return UpdateSceneMsg.SetBoxShadow(readUpdateSceneMsg_SetBoxShadow(sink))
+      return UpdateSceneMsg.SetBoxShadows(readUpdateSceneMsg_SetBoxShadows(sink))
     case 12:
       return UpdateSceneMsg.SetBackgroundColor(
         readUpdateSceneMsg_SetBackgroundColor(sink)
@@ -218,6 +445,34 @@ export const readUpdateSceneMsg = (sink: Sink): UpdateSceneMsg => {
       return UpdateSceneMsg.SetText(readUpdateSceneMsg_SetText(sink))
     case 15:
       return UpdateSceneMsg.SetBorder(readUpdateSceneMsg_SetBorder(sink))
+    case 16:
+      return UpdateSceneMsg.SetSvg(readUpdateSceneMsg_SetSvg(sink))
+    case 17:
+      return UpdateSceneMsg.SetOpacity(readUpdateSceneMsg_SetOpacity(sink))
+    case 18:
+      return UpdateSceneMsg.SetZIndex(readUpdateSceneMsg_SetZIndex(sink))
+    case 19:
+      return UpdateSceneMsg.SetTransform(readUpdateSceneMsg_SetTransform(sink))
+    case 20:
+      return UpdateSceneMsg.SetBackground(readUpdateSceneMsg_SetBackground(sink))
+    case 21:
+      return UpdateSceneMsg.SetTextInput(readUpdateSceneMsg_SetTextInput(sink))
+    case 22:
+      return UpdateSceneMsg.ObserveLayout(readUpdateSceneMsg_ObserveLayout(sink))
+    case 23:
+      return UpdateSceneMsg.SetPosition(readUpdateSceneMsg_SetPosition(sink))
+    case 24:
+      return UpdateSceneMsg.SetMinSize(readUpdateSceneMsg_SetMinSize(sink))
+    case 25:
+      return UpdateSceneMsg.SetMaxSize(readUpdateSceneMsg_SetMaxSize(sink))
+    case 26:
+      return UpdateSceneMsg.SetAspectRatio(
+        readUpdateSceneMsg_SetAspectRatio(sink)
+      )
+    case 27:
+      return UpdateSceneMsg.SetDisplay(readUpdateSceneMsg_SetDisplay(sink))
+    case 28:
+      return UpdateSceneMsg.SetVisibility(readUpdateSceneMsg_SetVisibility(sink))
   }
   throw new Error('bad variant index for UpdateSceneMsg')
 }
@@ -295,12 +550,12 @@ const readUpdateSceneMsg_SetMargin = (sink: Sink): UpdateSceneMsg_SetMargin => {
   return { surface, margin }
 }
 
-const readUpdateSceneMsg_SetBoxShadow = (
+const readUpdateSceneMsg_SetBoxShadows = (
   sink: Sink
-): UpdateSceneMsg_SetBoxShadow => {
+): UpdateSceneMsg_SetBoxShadows => {
   const surface = readSurfaceId(sink)
-  const boxShadow = readOptBoxShadow(sink)
-  return { surface, boxShadow }
+  const boxShadows = readVecBoxShadow(sink)
+  return { surface, boxShadows }
 }
 
 const readUpdateSceneMsg_SetBackgroundColor = (
@@ -329,10 +584,144 @@ const readUpdateSceneMsg_SetBorder = (sink: Sink): UpdateSceneMsg_SetBorder => {
   return { surface, border }
 }
 
+const readUpdateSceneMsg_SetSvg = (sink: Sink): UpdateSceneMsg_SetSvg => {
+  const surface = readSurfaceId(sink)
+  const svg = readOptSvg(sink)
+  return { surface, svg }
+}
+
+const readUpdateSceneMsg_SetOpacity = (
+  sink: Sink
+): UpdateSceneMsg_SetOpacity => {
+  const surface = readSurfaceId(sink)
+  const opacity = read_f32(sink)
+  return { surface, opacity }
+}
+
+const readUpdateSceneMsg_SetZIndex = (sink: Sink): UpdateSceneMsg_SetZIndex => {
+  const surface = readSurfaceId(sink)
+  const zIndex = read_i32(sink)
+  return { surface, zIndex }
+}
+
+const readUpdateSceneMsg_SetTransform = (
+  sink: Sink
+): UpdateSceneMsg_SetTransform => {
+  const surface = readSurfaceId(sink)
+  const transform = readOptTransform(sink)
+  return { surface, transform }
+}
+
+const readUpdateSceneMsg_SetBackground = (
+  sink: Sink
+): UpdateSceneMsg_SetBackground => {
+  const surface = readSurfaceId(sink)
+  const background = readOptBackground(sink)
+  return { surface, background }
+}
+
+const readUpdateSceneMsg_SetTextInput = (
+  sink: Sink
+): UpdateSceneMsg_SetTextInput => {
+  const surface = readSurfaceId(sink)
+  const textInput = readOptTextInput(sink)
+  return { surface, textInput }
+}
+
+const readUpdateSceneMsg_ObserveLayout = (
+  sink: Sink
+): UpdateSceneMsg_ObserveLayout => {
+  const surface = readSurfaceId(sink)
+  const observe = read_bool(sink)
+  return { surface, observe }
+}
+
+const readUpdateSceneMsg_SetPosition = (
+  sink: Sink
+): UpdateSceneMsg_SetPosition => {
+  const surface = readSurfaceId(sink)
+  const position = readPosition(sink)
+  return { surface, position }
+}
+
+const readUpdateSceneMsg_SetMinSize = (
+  sink: Sink
+): UpdateSceneMsg_SetMinSize => {
+  const surface = readSurfaceId(sink)
+  const size = readSize(sink)
+  return { surface, size }
+}
+
+const readUpdateSceneMsg_SetMaxSize = (
+  sink: Sink
+): UpdateSceneMsg_SetMaxSize => {
+  const surface = readSurfaceId(sink)
+  const size = readSize(sink)
+  return { surface, size }
+}
+
+const readUpdateSceneMsg_SetAspectRatio = (
+  sink: Sink
+): UpdateSceneMsg_SetAspectRatio => {
+  const surface = readSurfaceId(sink)
+  const aspectRatio = readOptF32(sink)
+  return { surface, aspectRatio }
+}
+
+const readUpdateSceneMsg_SetDisplay = (
+  sink: Sink
+): UpdateSceneMsg_SetDisplay => {
+  const surface = readSurfaceId(sink)
+  const display = readDisplay(sink)
+  return { surface, display }
+}
+
+const readUpdateSceneMsg_SetVisibility = (
+  sink: Sink
+): UpdateSceneMsg_SetVisibility => {
+  const surface = readSurfaceId(sink)
+  const visibility = readOptVisibility(sink)
+  return { surface, visibility }
+}
+
 export const readWindowId: Deserializer<WindowId> = read_u16
 
 export const readSurfaceId: Deserializer<SurfaceId> = read_u64
 
+const AnimatedPropertyReverseMap: AnimatedProperty[] = [
+  AnimatedProperty.Opacity,
+  AnimatedProperty.Transform,
+  AnimatedProperty.BackgroundColor,
+  AnimatedProperty.ScrollOffset
+]
+
+export const readAnimatedProperty = (sink: Sink): AnimatedProperty =>
+  AnimatedPropertyReverseMap[read_u32(sink)]
+
+export const readAnimatedValue = (sink: Sink): AnimatedValue => {
+  switch (read_u32(sink)) {
+    case 0:
+      return AnimatedValue.Opacity(read_f32(sink))
+    case 1:
+      return AnimatedValue.Transform(readTransform(sink))
+    case 2:
+      return AnimatedValue.BackgroundColor(readColor(sink))
+    case 3:
+      return AnimatedValue.ScrollOffset(readVector2f(sink))
+  }
+  throw new Error('bad variant index for AnimatedValue')
+}
+
+const EasingReverseMap: Easing[] = [
+  Easing.Linear,
+  Easing.EaseIn,
+  Easing.EaseOut,
+  Easing.EaseInOut
+]
+
+export const readEasing = (sink: Sink): Easing =>
+  EasingReverseMap[read_u32(sink)]
+
 export const readColor = (sink: Sink): Color =>
   Color(read_u8(sink), read_u8(sink), read_u8(sink), read_u8(sink))
 
@@ -381,6 +770,15 @@ const JustifyContentReverseMap: JustifyContent[] = [
 export const readJustifyContent = (sink: Sink): JustifyContent =>
   JustifyContentReverseMap[read_u32(sink)]
 
+const DirectionReverseMap: Direction[] = [
+  Direction.Inherit,
+  Direction.Ltr,
+  Direction.Rtl
+]
+
+export const readDirection = (sink: Sink): Direction =>
+  DirectionReverseMap[read_u32(sink)]
+
 export const readFlow = (sink: Sink): Flow => {
   const flexDirection = readFlexDirection(sink)
   const flexWrap = readFlexWrap(sink)
@@ -388,13 +786,15 @@ export const readFlow = (sink: Sink): Flow => {
   const alignItems = readFlexAlign(sink)
   const alignSelf = readFlexAlign(sink)
   const justifyContent = readJustifyContent(sink)
+  const direction = readDirection(sink)
   return {
     flexDirection,
     flexWrap,
     alignContent,
     alignItems,
     alignSelf,
-    justifyContent
+    justifyContent,
+    direction
   }
 }
 
@@ -426,12 +826,48 @@ const OverflowReverseMap: Overflow[] = [
 export const readOverflow = (sink: Sink): Overflow =>
   OverflowReverseMap[read_u32(sink)]
 
+const PositionTypeReverseMap: PositionType[] = [
+  PositionType.Relative,
+  PositionType.Absolute
+]
+
+export const readPositionType = (sink: Sink): PositionType =>
+  PositionTypeReverseMap[read_u32(sink)]
+
+export const readPosition = (sink: Sink): Position => {
+  const kind = readPositionType(sink)
+  const top = readDimension(sink)
+  const right = readDimension(sink)
+  const bottom = readDimension(sink)
+  const left = readDimension(sink)
+  return { kind, top, right, bottom, left }
+}
+
+const DisplayReverseMap: Display[] = [Display.Flex, Display.None]
+
+export const readDisplay = (sink: Sink): Display =>
+  DisplayReverseMap[read_u32(sink)]
+
+const VisibilityReverseMap: Visibility[] = [
+  Visibility.Visible,
+  Visibility.Hidden
+]
+
+export const readVisibility = (sink: Sink): Visibility =>
+  VisibilityReverseMap[read_u32(sink)]
+
 export const readSize = (sink: Sink): Size =>
   Size(readDimension(sink), readDimension(sink))
 
 export const readRect = (sink: Sink): Rect =>
   Rect(read_f32(sink), read_f32(sink), read_f32(sink), read_f32(sink))
 
+export const readSurfaceLayout = (sink: Sink): SurfaceLayout => {
+  const rect = readRect(sink)
+  const absolute = readRect(sink)
+  return { rect, absolute }
+}
+
 export const readDimensions = (sink: Sink): Dimensions =>
   Dimensions(
     readDimension(sink),
@@ -443,15 +879,97 @@ export const readDimensions = (sink: Sink): Dimensions =>
 export const readVector2f = (sink: Sink): Vector2f =>
   Vector2f(read_f32(sink), read_f32(sink))
 
+export const readCornerRadius = (sink: Sink): CornerRadius =>
+  CornerRadius(readDimension(sink), readDimension(sink))
+
 export const readBorderRadius = (sink: Sink): BorderRadius =>
-  BorderRadius(read_f32(sink), read_f32(sink), read_f32(sink), read_f32(sink))
+  BorderRadius(
+    readCornerRadius(sink),
+    readCornerRadius(sink),
+    readCornerRadius(sink),
+    readCornerRadius(sink)
+  )
 
 export const readBoxShadow = (sink: Sink): BoxShadow => {
   const color = readColor(sink)
   const offset = readVector2f(sink)
   const blur = read_f32(sink)
   const spread = read_f32(sink)
-  return { color, offset, blur, spread }
+  const inset = read_bool(sink)
+  return { color, offset, blur, spread, inset }
+}
+
+export const readMatrix = (sink: Sink): Matrix =>
+  Matrix(
+    read_f32(sink),
+    read_f32(sink),
+    read_f32(sink),
+    read_f32(sink),
+    read_f32(sink),
+    read_f32(sink)
+  )
+
+export const readTransformOp = (sink: Sink): TransformOp => {
+  switch (read_u32(sink)) {
+    case 0:
+      return TransformOp.Matrix(readMatrix(sink))
+    case 1:
+      return TransformOp.Translate(readVector2f(sink))
+    case 2:
+      return TransformOp.Scale(readVector2f(sink))
+    case 3:
+      return TransformOp.Rotate(read_f32(sink))
+    case 4:
+      return TransformOp.Skew(readVector2f(sink))
+  }
+  throw new Error('bad variant index for TransformOp')
+}
+
+export const readTransformOrigin = (sink: Sink): TransformOrigin =>
+  TransformOrigin(readDimension(sink), readDimension(sink))
+
+export const readTransform = (sink: Sink): Transform => {
+  const ops = readVecTransformOp(sink)
+  const origin = readTransformOrigin(sink)
+  return { ops, origin }
+}
+
+export const readColorStop = (sink: Sink): ColorStop => {
+  const offset = read_f32(sink)
+  const color = readColor(sink)
+  return { offset, color }
+}
+
+export const readLinearGradient = (sink: Sink): LinearGradient => {
+  const angle = read_f32(sink)
+  const stops = readVecColorStop(sink)
+  return { angle, stops }
+}
+
+export const readRadialGradient = (sink: Sink): RadialGradient => {
+  const center = readVector2f(sink)
+  const radius = readVector2f(sink)
+  const stops = readVecColorStop(sink)
+  return { center, radius, stops }
+}
+
+export const readConicGradient = (sink: Sink): ConicGradient => {
+  const center = readVector2f(sink)
+  const angle = read_f32(sink)
+  const stops = readVecColorStop(sink)
+  return { center, angle, stops }
+}
+
+export const readBackground = (sink: Sink): Background => {
+  switch (read_u32(sink)) {
+    case 0:
+      return Background.LinearGradient(readLinearGradient(sink))
+    case 1:
+      return Background.RadialGradient(readRadialGradient(sink))
+    case 2:
+      return Background.ConicGradient(readConicGradient(sink))
+  }
+  throw new Error('bad variant index for Background')
 }
 
 export const readImage = (sink: Sink): Image => {
@@ -459,22 +977,192 @@ export const readImage = (sink: Sink): Image => {
   return { url }
 }
 
+export const readSvg = (sink: Sink): Svg => {
+  switch (read_u32(sink)) {
+    case 0:
+      return Svg.Source(read_str(sink))
+    case 1:
+      return Svg.Path(read_str(sink))
+  }
+  throw new Error('bad variant index for Svg')
+}
+
 const TextAlignReverseMap: TextAlign[] = [
   TextAlign.Left,
   TextAlign.Center,
-  TextAlign.Right
+  TextAlign.Right,
+  TextAlign.Justify
 ]
 
 export const readTextAlign = (sink: Sink): TextAlign =>
   TextAlignReverseMap[read_u32(sink)]
 
+const EllipsizeReverseMap: Ellipsize[] = [
+  Ellipsize.None,
+  Ellipsize.Start,
+  Ellipsize.Middle,
+  Ellipsize.End
+]
+
+export const readEllipsize = (sink: Sink): Ellipsize =>
+  EllipsizeReverseMap[read_u32(sink)]
+
+const WhiteSpaceReverseMap: WhiteSpace[] = [
+  WhiteSpace.Normal,
+  WhiteSpace.NoWrap,
+  WhiteSpace.Pre,
+  WhiteSpace.PreWrap,
+  WhiteSpace.PreLine
+]
+
+export const readWhiteSpace = (sink: Sink): WhiteSpace =>
+  WhiteSpaceReverseMap[read_u32(sink)]
+
+const WordBreakReverseMap: WordBreak[] = [
+  WordBreak.Normal,
+  WordBreak.BreakAll,
+  WordBreak.BreakWord
+]
+
+export const readWordBreak = (sink: Sink): WordBreak =>
+  WordBreakReverseMap[read_u32(sink)]
+
+const FontStyleReverseMap: FontStyle[] = [FontStyle.Normal, FontStyle.Italic]
+
+export const readFontStyle = (sink: Sink): FontStyle =>
+  FontStyleReverseMap[read_u32(sink)]
+
+export const readTextSpan = (sink: Sink): TextSpan => {
+  const start = read_u32(sink)
+  const end = read_u32(sink)
+  const color = readOptColor(sink)
+  const fontSize = readOptF32(sink)
+  const fontWeight = readOptU32(sink)
+  const fontStyle = readOptFontStyle(sink)
+  const underline = read_bool(sink)
+  const link = readOptU32(sink)
+  return {
+    start,
+    end,
+    color,
+    fontSize,
+    fontWeight,
+    fontStyle,
+    underline,
+    link
+  }
+}
+
+const TextDecorationStyleReverseMap: TextDecorationStyle[] = [
+  TextDecorationStyle.Solid,
+  TextDecorationStyle.Double,
+  TextDecorationStyle.Dotted,
+  TextDecorationStyle.Dashed,
+  TextDecorationStyle.Wavy
+]
+
+export const readTextDecorationStyle = (sink: Sink): TextDecorationStyle =>
+  TextDecorationStyleReverseMap[read_u32(sink)]
+
+export const readTextDecoration = (sink: Sink): TextDecoration => {
+  const underline = read_bool(sink)
+  const lineThrough = read_bool(sink)
+  const overline = read_bool(sink)
+  const color = readOptColor(sink)
+  const style = readTextDecorationStyle(sink)
+  return { underline, lineThrough, overline, color, style }
+}
+
+export const readTextShadow = (sink: Sink): TextShadow => {
+  const offset = readVector2f(sink)
+  const blur = read_f32(sink)
+  const color = readColor(sink)
+  return { offset, blur, color }
+}
+
+export const readFontFeature = (sink: Sink): FontFeature => {
+  const tag = read_str(sink)
+  const value = read_u32(sink)
+  return { tag, value }
+}
+
+export const readFontVariation = (sink: Sink): FontVariation => {
+  const tag = read_str(sink)
+  const value = read_f32(sink)
+  return { tag, value }
+}
+
 export const readText = (sink: Sink): Text => {
   const color = readColor(sink)
   const fontSize = read_f32(sink)
   const lineHeight = read_f32(sink)
   const align = readTextAlign(sink)
+  const numberOfLines = read_u32(sink)
+  const ellipsize = readEllipsize(sink)
+  const whiteSpace = readWhiteSpace(sink)
+  const wordBreak = readWordBreak(sink)
+  const spans = readVecTextSpan(sink)
+  const decoration = readOptTextDecoration(sink)
+  const shadows = readVecTextShadow(sink)
+  const letterSpacing = read_f32(sink)
+  const fontFeatures = readVecFontFeature(sink)
+  const fontVariations = readVecFontVariation(sink)
+  const direction = readDirection(sink)
   const text = read_str(sink)
-  return { color, fontSize, lineHeight, align, text }
+  return {
+    color,
+    fontSize,
+    lineHeight,
+    align,
+    numberOfLines,
+    ellipsize,
+    whiteSpace,
+    wordBreak,
+    spans,
+    decoration,
+    shadows,
+    letterSpacing,
+    fontFeatures,
+    fontVariations,
+    direction,
+    text
+  }
+}
+
+export const readTextInput = (sink: Sink): TextInput => {
+  const value = read_str(sink)
+  const placeholder = read_str(sink)
+  const placeholderColor = readOptColor(sink)
+  const secure = read_bool(sink)
+  const multiline = read_bool(sink)
+  return { value, placeholder, placeholderColor, secure, multiline }
+}
+
+export const readTextLine = (sink: Sink): TextLine => {
+  const rect = readRect(sink)
+  const start = read_u64(sink)
+  const baseline = read_f32(sink)
+  return { rect, start, baseline }
+}
+
+export const readGlyphBox = (sink: Sink): GlyphBox => {
+  const rect = readRect(sink)
+  const line = read_u64(sink)
+  const start = read_u64(sink)
+  const end = read_u64(sink)
+  return { rect, line, start, end }
+}
+
+export const readTextLayout = (sink: Sink): TextLayout => {
+  const width = read_f32(sink)
+  const height = read_f32(sink)
+  const baseline = read_f32(sink)
+  const ascent = read_f32(sink)
+  const descent = read_f32(sink)
+  const truncated = read_bool(sink)
+  const lines = readVecTextLine(sink)
+  const glyphs = readVecGlyphBox(sink)
+  return { width, height, baseline, ascent, descent, truncated, lines, glyphs }
 }
 
 export const readBorder = (sink: Sink): Border => {
@@ -482,7 +1170,8 @@ export const readBorder = (sink: Sink): Border => {
   const right = readBorderSide(sink)
   const bottom = readBorderSide(sink)
   const left = readBorderSide(sink)
-  return { top, right, bottom, left }
+  const image = readOptBorderImage(sink)
+  return { top, right, bottom, left, image }
 }
 
 export const readBorderSide = (sink: Sink): BorderSide => {
@@ -494,8 +1183,48 @@ export const readBorderSide = (sink: Sink): BorderSide => {
 
 const BorderStyleReverseMap: BorderStyle[] = [
   BorderStyle.None,
-  BorderStyle.Solid
+  BorderStyle.Solid,
+  BorderStyle.Dashed,
+  BorderStyle.Dotted,
+  BorderStyle.Double,
+  BorderStyle.Groove,
+  BorderStyle.Ridge,
+  BorderStyle.Inset,
+  BorderStyle.Outset
 ]
 
 export const readBorderStyle = (sink: Sink): BorderStyle =>
   BorderStyleReverseMap[read_u32(sink)]
+
+export const readBorderImage = (sink: Sink): BorderImage => {
+  const source = readBorderImageSource(sink)
+  const slice = readBorderImageSlice(sink)
+  const fill = read_bool(sink)
+  const repeat = readBorderImageRepeat(sink)
+  return { source, slice, fill, repeat }
+}
+
+export const readBorderImageSource = (sink: Sink): BorderImageSource => {
+  switch (read_u32(sink)) {
+    case 0:
+      return BorderImageSource.Image(readImage(sink))
+    case 1:
+      return BorderImageSource.LinearGradient(readLinearGradient(sink))
+    case 2:
+      return BorderImageSource.RadialGradient(readRadialGradient(sink))
+  }
+  throw new Error('bad variant index for BorderImageSource')
+}
+
+export const readBorderImageSlice = (sink: Sink): BorderImageSlice =>
+  BorderImageSlice(read_u32(sink), read_u32(sink), read_u32(sink), read_u32(sink))
+
+const BorderImageRepeatReverseMap: BorderImageRepeat[] = [
+  BorderImageRepeat.Stretch,
+  BorderImageRepeat.Repeat,
+  BorderImageRepeat.Round,
+  BorderImageRepeat.Space
+]
+
+export const readBorderImageRepeat = (sink: Sink): BorderImageRepeat =>
+  BorderImageRepeatReverseMap[read_u32(sink)]
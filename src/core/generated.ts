@@ -2,12 +2,58 @@ export type FfiMsg =
   | { tag: 'GetEvents'; value: boolean }
   | { tag: 'CreateWindow' }
   | { tag: 'UpdateScene'; value: FfiMsg_UpdateScene }
+  | { tag: 'Animate'; value: FfiMsg_Animate }
+  | { tag: 'TextOffsetAt'; value: FfiMsg_TextOffsetAt }
+  | { tag: 'CaretRect'; value: FfiMsg_CaretRect }
+  | { tag: 'MeasureText'; value: FfiMsg_MeasureText }
+  | { tag: 'GetTextLayout'; value: FfiMsg_GetTextLayout }
+  | { tag: 'GetLayout'; value: FfiMsg_GetLayout }
 
 export interface FfiMsg_UpdateScene {
   window: WindowId
   msgs: Array<UpdateSceneMsg>
 }
 
+export interface FfiMsg_Animate {
+  window: WindowId
+  surface: SurfaceId
+  property: AnimatedProperty
+  from: AnimatedValue
+  to: AnimatedValue
+  duration: number
+  easing: Easing
+  delay: number
+  iterations: number
+}
+
+export interface FfiMsg_TextOffsetAt {
+  window: WindowId
+  surface: SurfaceId
+  x: number
+  y: number
+}
+
+export interface FfiMsg_CaretRect {
+  window: WindowId
+  surface: SurfaceId
+  offset: number
+}
+
+export interface FfiMsg_MeasureText {
+  text: Text
+  maxWidth: (number) | undefined
+}
+
+export interface FfiMsg_GetTextLayout {
+  window: WindowId
+  surface: SurfaceId
+}
+
+export interface FfiMsg_GetLayout {
+  window: WindowId
+  surfaces: Array<SurfaceId>
+}
+
 export module FfiMsg {
   export const GetEvents = (value: boolean): FfiMsg => ({
     tag: 'GetEvents',
@@ -20,12 +66,47 @@ export module FfiMsg {
     tag: 'UpdateScene',
     value
   })
+
+  export const Animate = (value: FfiMsg_Animate): FfiMsg => ({
+    tag: 'Animate',
+    value
+  })
+
+  export const TextOffsetAt = (value: FfiMsg_TextOffsetAt): FfiMsg => ({
+    tag: 'TextOffsetAt',
+    value
+  })
+
+  export const CaretRect = (value: FfiMsg_CaretRect): FfiMsg => ({
+    tag: 'CaretRect',
+    value
+  })
+
+  export const MeasureText = (value: FfiMsg_MeasureText): FfiMsg => ({
+    tag: 'MeasureText',
+    value
+  })
+
+  export const GetTextLayout = (value: FfiMsg_GetTextLayout): FfiMsg => ({
+    tag: 'GetTextLayout',
+    value
+  })
+
+  export const GetLayout = (value: FfiMsg_GetLayout): FfiMsg => ({
+    tag: 'GetLayout',
+    value
+  })
 }
 
 export type FfiResult =
   | { tag: 'Nothing' }
   | { tag: 'Events'; value: Array<Event> }
   | { tag: 'WindowId'; value: WindowId }
+  | { tag: 'TextOffset'; value: number }
+  | { tag: 'Rect'; value: Rect }
+  | { tag: 'TextLayout'; value: TextLayout }
+  | { tag: 'Layouts'; value: Array<SurfaceLayout> }
+  | { tag: 'Pending'; value: number }
 
 export module FfiResult {
   export const Nothing: FfiResult = { tag: 'Nothing' }
@@ -39,6 +120,31 @@ export module FfiResult {
     tag: 'WindowId',
     value
   })
+
+  export const TextOffset = (value: number): FfiResult => ({
+    tag: 'TextOffset',
+    value
+  })
+
+  export const Rect = (value: Rect): FfiResult => ({
+    tag: 'Rect',
+    value
+  })
+
+  export const TextLayout = (value: TextLayout): FfiResult => ({
+    tag: 'TextLayout',
+    value
+  })
+
+  export const Layouts = (value: Array<SurfaceLayout>): FfiResult => ({
+    tag: 'Layouts',
+    value
+  })
+
+  export const Pending = (value: number): FfiResult => ({
+    tag: 'Pending',
+    value
+  })
 }
 
 export type Event = { tag: 'WindowEvent'; value: Event_WindowEvent }
@@ -68,23 +174,44 @@ export type WindowEvent =
   | { tag: 'Resize' }
   | { tag: 'Close' }
   | { tag: 'Unknown' }
+  | { tag: 'AnimationEnd'; value: WindowEvent_AnimationEnd }
+  | { tag: 'TextChanged'; value: WindowEvent_TextChanged }
+  | { tag: 'LayoutChanged'; value: WindowEvent_LayoutChanged }
 
 export interface WindowEvent_MouseMove {
   target: number
+  link: (number) | undefined
 }
 
 export interface WindowEvent_MouseDown {
   target: number
+  link: (number) | undefined
 }
 
 export interface WindowEvent_MouseUp {
   target: number
+  link: (number) | undefined
 }
 
 export interface WindowEvent_Scroll {
   target: number
 }
 
+export interface WindowEvent_AnimationEnd {
+  target: number
+  property: AnimatedProperty
+}
+
+export interface WindowEvent_TextChanged {
+  target: number
+  value: string
+}
+
+export interface WindowEvent_LayoutChanged {
+  target: number
+  rect: Rect
+}
+
 export module WindowEvent {
   export const MouseMove = (value: WindowEvent_MouseMove): WindowEvent => ({
     tag: 'MouseMove',
@@ -127,6 +254,18 @@ export module WindowEvent {
   export const Close: WindowEvent = { tag: 'Close' }
 
   export const Unknown: WindowEvent = { tag: 'Unknown' }
+
+  export const AnimationEnd = (
+    value: WindowEvent_AnimationEnd
+  ): WindowEvent => ({ tag: 'AnimationEnd', value })
+
+  export const TextChanged = (
+    value: WindowEvent_TextChanged
+  ): WindowEvent => ({ tag: 'TextChanged', value })
+
+  export const LayoutChanged = (
+    value: WindowEvent_LayoutChanged
+  ): WindowEvent => ({ tag: 'LayoutChanged', value })
 }
 
 export type UpdateSceneMsg =
@@ -141,11 +280,24 @@ export type UpdateSceneMsg =
   | { tag: 'SetFlow'; value: UpdateSceneMsg_SetFlow }
   | { tag: 'SetPadding'; value: UpdateSceneMsg_SetPadding }
   | { tag: 'SetMargin'; value: UpdateSceneMsg_SetMargin }
-  | { tag: 'SetBoxShadow'; value: UpdateSceneMsg_SetBoxShadow }
+  | { tag: 'SetBoxShadows'; value: UpdateSceneMsg_SetBoxShadows }
   | { tag: 'SetBackgroundColor'; value: UpdateSceneMsg_SetBackgroundColor }
   | { tag: 'SetImage'; value: UpdateSceneMsg_SetImage }
   | { tag: 'SetText'; value: UpdateSceneMsg_SetText }
   | { tag: 'SetBorder'; value: UpdateSceneMsg_SetBorder }
+  | { tag: 'SetSvg'; value: UpdateSceneMsg_SetSvg }
+  | { tag: 'SetOpacity'; value: UpdateSceneMsg_SetOpacity }
+  | { tag: 'SetZIndex'; value: UpdateSceneMsg_SetZIndex }
+  | { tag: 'SetTransform'; value: UpdateSceneMsg_SetTransform }
+  | { tag: 'SetBackground'; value: UpdateSceneMsg_SetBackground }
+  | { tag: 'SetTextInput'; value: UpdateSceneMsg_SetTextInput }
+  | { tag: 'ObserveLayout'; value: UpdateSceneMsg_ObserveLayout }
+  | { tag: 'SetPosition'; value: UpdateSceneMsg_SetPosition }
+  | { tag: 'SetMinSize'; value: UpdateSceneMsg_SetMinSize }
+  | { tag: 'SetMaxSize'; value: UpdateSceneMsg_SetMaxSize }
+  | { tag: 'SetAspectRatio'; value: UpdateSceneMsg_SetAspectRatio }
+  | { tag: 'SetDisplay'; value: UpdateSceneMsg_SetDisplay }
+  | { tag: 'SetVisibility'; value: UpdateSceneMsg_SetVisibility }
 
 export interface UpdateSceneMsg_AppendChild {
   parent: SurfaceId
@@ -198,9 +350,9 @@ export interface UpdateSceneMsg_SetMargin {
   margin: Dimensions
 }
 
-export interface UpdateSceneMsg_SetBoxShadow {
+export interface UpdateSceneMsg_SetBoxShadows {
   surface: SurfaceId
-  boxShadow: (BoxShadow) | undefined
+  boxShadows: Array<BoxShadow>
 }
 
 export interface UpdateSceneMsg_SetBackgroundColor {
@@ -223,6 +375,71 @@ export interface UpdateSceneMsg_SetBorder {
   border: (Border) | undefined
 }
 
+export interface UpdateSceneMsg_SetSvg {
+  surface: SurfaceId
+  svg: (Svg) | undefined
+}
+
+export interface UpdateSceneMsg_SetOpacity {
+  surface: SurfaceId
+  opacity: number
+}
+
+export interface UpdateSceneMsg_SetZIndex {
+  surface: SurfaceId
+  zIndex: number
+}
+
+export interface UpdateSceneMsg_SetTransform {
+  surface: SurfaceId
+  transform: (Transform) | undefined
+}
+
+export interface UpdateSceneMsg_SetBackground {
+  surface: SurfaceId
+  background: (Background) | undefined
+}
+
+export interface UpdateSceneMsg_SetTextInput {
+  surface: SurfaceId
+  textInput: (TextInput) | undefined
+}
+
+export interface UpdateSceneMsg_ObserveLayout {
+  surface: SurfaceId
+  observe: boolean
+}
+
+export interface UpdateSceneMsg_SetPosition {
+  surface: SurfaceId
+  position: Position
+}
+
+export interface UpdateSceneMsg_SetMinSize {
+  surface: SurfaceId
+  size: Size
+}
+
+export interface UpdateSceneMsg_SetMaxSize {
+  surface: SurfaceId
+  size: Size
+}
+
+export interface UpdateSceneMsg_SetAspectRatio {
+  surface: SurfaceId
+  aspectRatio: (number) | undefined
+}
+
+export interface UpdateSceneMsg_SetDisplay {
+  surface: SurfaceId
+  display: Display
+}
+
+export interface UpdateSceneMsg_SetVisibility {
+  surface: SurfaceId
+  visibility: (Visibility) | undefined
+}
+
 export module UpdateSceneMsg {
   export const Alloc: UpdateSceneMsg = { tag: 'Alloc' }
 
@@ -269,9 +486,9 @@ export module UpdateSceneMsg {
     value: UpdateSceneMsg_SetMargin
   ): UpdateSceneMsg => ({ tag: 'SetMargin', value })
 
-  export const SetBoxShadow = (
-    value: UpdateSceneMsg_SetBoxShadow
-  ): UpdateSceneMsg => ({ tag: 'SetBoxShadow', value })
+  export const SetBoxShadows = (
+    value: UpdateSceneMsg_SetBoxShadows
+  ): UpdateSceneMsg => ({ tag: 'SetBoxShadows', value })
 
   export const SetBackgroundColor = (
     value: UpdateSceneMsg_SetBackgroundColor
@@ -290,12 +507,107 @@ export module UpdateSceneMsg {
   export const SetBorder = (
     value: UpdateSceneMsg_SetBorder
   ): UpdateSceneMsg => ({ tag: 'SetBorder', value })
+
+  export const SetSvg = (value: UpdateSceneMsg_SetSvg): UpdateSceneMsg => ({
+    tag: 'SetSvg',
+    value
+  })
+
+  export const SetOpacity = (
+    value: UpdateSceneMsg_SetOpacity
+  ): UpdateSceneMsg => ({ tag: 'SetOpacity', value })
+
+  export const SetZIndex = (
+    value: UpdateSceneMsg_SetZIndex
+  ): UpdateSceneMsg => ({ tag: 'SetZIndex', value })
+
+  export const SetTransform = (
+    value: UpdateSceneMsg_SetTransform
+  ): UpdateSceneMsg => ({ tag: 'SetTransform', value })
+
+  export const SetBackground = (
+    value: UpdateSceneMsg_SetBackground
+  ): UpdateSceneMsg => ({ tag: 'SetBackground', value })
+
+  export const SetTextInput = (
+    value: UpdateSceneMsg_SetTextInput
+  ): UpdateSceneMsg => ({ tag: 'SetTextInput', value })
+
+  export const ObserveLayout = (
+    value: UpdateSceneMsg_ObserveLayout
+  ): UpdateSceneMsg => ({ tag: 'ObserveLayout', value })
+
+  export const SetPosition = (
+    value: UpdateSceneMsg_SetPosition
+  ): UpdateSceneMsg => ({ tag: 'SetPosition', value })
+
+  export const SetMinSize = (
+    value: UpdateSceneMsg_SetMinSize
+  ): UpdateSceneMsg => ({ tag: 'SetMinSize', value })
+
+  export const SetMaxSize = (
+    value: UpdateSceneMsg_SetMaxSize
+  ): UpdateSceneMsg => ({ tag: 'SetMaxSize', value })
+
+  export const SetAspectRatio = (
+    value: UpdateSceneMsg_SetAspectRatio
+  ): UpdateSceneMsg => ({ tag: 'SetAspectRatio', value })
+
+  export const SetDisplay = (
+    value: UpdateSceneMsg_SetDisplay
+  ): UpdateSceneMsg => ({ tag: 'SetDisplay', value })
+
+  export const SetVisibility = (
+    value: UpdateSceneMsg_SetVisibility
+  ): UpdateSceneMsg => ({ tag: 'SetVisibility', value })
 }
 
 export type WindowId = number
 
 export type SurfaceId = number
 
+export enum AnimatedProperty {
+  Opacity = 'Opacity',
+  Transform = 'Transform',
+  BackgroundColor = 'BackgroundColor',
+  ScrollOffset = 'ScrollOffset'
+}
+
+export type AnimatedValue =
+  | { tag: 'Opacity'; value: number }
+  | { tag: 'Transform'; value: Transform }
+  | { tag: 'BackgroundColor'; value: Color }
+  | { tag: 'ScrollOffset'; value: Vector2f }
+
+export module AnimatedValue {
+  export const Opacity = (value: number): AnimatedValue => ({
+    tag: 'Opacity',
+    value
+  })
+
+  export const Transform = (value: Transform): AnimatedValue => ({
+    tag: 'Transform',
+    value
+  })
+
+  export const BackgroundColor = (value: Color): AnimatedValue => ({
+    tag: 'BackgroundColor',
+    value
+  })
+
+  export const ScrollOffset = (value: Vector2f): AnimatedValue => ({
+    tag: 'ScrollOffset',
+    value
+  })
+}
+
+export enum Easing {
+  Linear = 'Linear',
+  EaseIn = 'EaseIn',
+  EaseOut = 'EaseOut',
+  EaseInOut = 'EaseInOut'
+}
+
 export interface Color {
   0: number
   1: number
@@ -344,6 +656,12 @@ export enum JustifyContent {
   SpaceEvenly = 'SpaceEvenly'
 }
 
+export enum Direction {
+  Inherit = 'Inherit',
+  Ltr = 'Ltr',
+  Rtl = 'Rtl'
+}
+
 export interface Flow {
   flexDirection: FlexDirection
   flexWrap: FlexWrap
@@ -351,6 +669,7 @@ export interface Flow {
   alignItems: FlexAlign
   alignSelf: FlexAlign
   justifyContent: JustifyContent
+  direction: Direction
 }
 
 export interface Flex {
@@ -381,6 +700,29 @@ export enum Overflow {
   Scroll = 'Scroll'
 }
 
+export enum PositionType {
+  Relative = 'Relative',
+  Absolute = 'Absolute'
+}
+
+export interface Position {
+  kind: PositionType
+  top: Dimension
+  right: Dimension
+  bottom: Dimension
+  left: Dimension
+}
+
+export enum Display {
+  Flex = 'Flex',
+  None = 'None'
+}
+
+export enum Visibility {
+  Visible = 'Visible',
+  Hidden = 'Hidden'
+}
+
 export interface Size {
   0: Dimension
   1: Dimension
@@ -404,6 +746,11 @@ export const Rect = (p0: number, p1: number, p2: number, p3: number): Rect => [
   p3
 ]
 
+export interface SurfaceLayout {
+  rect: Rect
+  absolute: Rect
+}
+
 export interface Dimensions {
   0: Dimension
   1: Dimension
@@ -427,19 +774,30 @@ export interface Vector2f {
 
 export const Vector2f = (p0: number, p1: number): Vector2f => [p0, p1]
 
+export interface CornerRadius {
+  0: Dimension
+  1: Dimension
+  length: 2
+}
+
+export const CornerRadius = (p0: Dimension, p1: Dimension): CornerRadius => [
+  p0,
+  p1
+]
+
 export interface BorderRadius {
-  0: number
-  1: number
-  2: number
-  3: number
+  0: CornerRadius
+  1: CornerRadius
+  2: CornerRadius
+  3: CornerRadius
   length: 4
 }
 
 export const BorderRadius = (
-  p0: number,
-  p1: number,
-  p2: number,
-  p3: number
+  p0: CornerRadius,
+  p1: CornerRadius,
+  p2: CornerRadius,
+  p3: CornerRadius
 ): BorderRadius => [p0, p1, p2, p3]
 
 export interface BoxShadow {
@@ -447,16 +805,210 @@ export interface BoxShadow {
   offset: Vector2f
   blur: number
   spread: number
+  inset: boolean
+}
+
+export interface Matrix {
+  0: number
+  1: number
+  2: number
+  3: number
+  4: number
+  5: number
+  length: 6
+}
+
+export const Matrix = (
+  p0: number,
+  p1: number,
+  p2: number,
+  p3: number,
+  p4: number,
+  p5: number
+): Matrix => [p0, p1, p2, p3, p4, p5]
+
+export type TransformOp =
+  | { tag: 'Matrix'; value: Matrix }
+  | { tag: 'Translate'; value: Vector2f }
+  | { tag: 'Scale'; value: Vector2f }
+  | { tag: 'Rotate'; value: number }
+  | { tag: 'Skew'; value: Vector2f }
+
+export module TransformOp {
+  export const Matrix = (value: Matrix): TransformOp => ({
+    tag: 'Matrix',
+    value
+  })
+
+  export const Translate = (value: Vector2f): TransformOp => ({
+    tag: 'Translate',
+    value
+  })
+
+  export const Scale = (value: Vector2f): TransformOp => ({
+    tag: 'Scale',
+    value
+  })
+
+  export const Rotate = (value: number): TransformOp => ({
+    tag: 'Rotate',
+    value
+  })
+
+  export const Skew = (value: Vector2f): TransformOp => ({
+    tag: 'Skew',
+    value
+  })
+}
+
+export interface TransformOrigin {
+  0: Dimension
+  1: Dimension
+  length: 2
+}
+
+export const TransformOrigin = (
+  p0: Dimension,
+  p1: Dimension
+): TransformOrigin => [p0, p1]
+
+export interface Transform {
+  ops: Array<TransformOp>
+  origin: TransformOrigin
+}
+
+export interface ColorStop {
+  offset: number
+  color: Color
+}
+
+export interface LinearGradient {
+  angle: number
+  stops: Array<ColorStop>
+}
+
+export interface RadialGradient {
+  center: Vector2f
+  radius: Vector2f
+  stops: Array<ColorStop>
+}
+
+export interface ConicGradient {
+  center: Vector2f
+  angle: number
+  stops: Array<ColorStop>
+}
+
+export type Background =
+  | { tag: 'LinearGradient'; value: LinearGradient }
+  | { tag: 'RadialGradient'; value: RadialGradient }
+  | { tag: 'ConicGradient'; value: ConicGradient }
+
+export module Background {
+  export const LinearGradient = (value: LinearGradient): Background => ({
+    tag: 'LinearGradient',
+    value
+  })
+
+  export const RadialGradient = (value: RadialGradient): Background => ({
+    tag: 'RadialGradient',
+    value
+  })
+
+  export const ConicGradient = (value: ConicGradient): Background => ({
+    tag: 'ConicGradient',
+    value
+  })
 }
 
 export interface Image {
   url: string
 }
 
+export type Svg =
+  | { tag: 'Source'; value: string }
+  | { tag: 'Path'; value: string }
+
+export module Svg {
+  export const Source = (value: string): Svg => ({ tag: 'Source', value })
+
+  export const Path = (value: string): Svg => ({ tag: 'Path', value })
+}
+
 export enum TextAlign {
   Left = 'Left',
   Center = 'Center',
-  Right = 'Right'
+  Right = 'Right',
+  Justify = 'Justify'
+}
+
+export enum Ellipsize {
+  None = 'None',
+  Start = 'Start',
+  Middle = 'Middle',
+  End = 'End'
+}
+
+export enum WhiteSpace {
+  Normal = 'Normal',
+  NoWrap = 'NoWrap',
+  Pre = 'Pre',
+  PreWrap = 'PreWrap',
+  PreLine = 'PreLine'
+}
+
+export enum WordBreak {
+  Normal = 'Normal',
+  BreakAll = 'BreakAll',
+  BreakWord = 'BreakWord'
+}
+
+export enum FontStyle {
+  Normal = 'Normal',
+  Italic = 'Italic'
+}
+
+export interface TextSpan {
+  start: number
+  end: number
+  color: (Color) | undefined
+  fontSize: (number) | undefined
+  fontWeight: (number) | undefined
+  fontStyle: (FontStyle) | undefined
+  underline: boolean
+  link: (number) | undefined
+}
+
+export enum TextDecorationStyle {
+  Solid = 'Solid',
+  Double = 'Double',
+  Dotted = 'Dotted',
+  Dashed = 'Dashed',
+  Wavy = 'Wavy'
+}
+
+export interface TextDecoration {
+  underline: boolean
+  lineThrough: boolean
+  overline: boolean
+  color: (Color) | undefined
+  style: TextDecorationStyle
+}
+
+export interface TextShadow {
+  offset: Vector2f
+  blur: number
+  color: Color
+}
+
+export interface FontFeature {
+  tag: string
+  value: number
+}
+
+export interface FontVariation {
+  tag: string
+  value: number
 }
 
 export interface Text {
@@ -464,14 +1016,58 @@ export interface Text {
   fontSize: number
   lineHeight: number
   align: TextAlign
+  numberOfLines: number
+  ellipsize: Ellipsize
+  whiteSpace: WhiteSpace
+  wordBreak: WordBreak
+  spans: Array<TextSpan>
+  decoration: (TextDecoration) | undefined
+  shadows: Array<TextShadow>
+  letterSpacing: number
+  fontFeatures: Array<FontFeature>
+  fontVariations: Array<FontVariation>
+  direction: Direction
   text: string
 }
 
+export interface TextInput {
+  value: string
+  placeholder: string
+  placeholderColor: (Color) | undefined
+  secure: boolean
+  multiline: boolean
+}
+
+export interface TextLine {
+  rect: Rect
+  start: number
+  baseline: number
+}
+
+export interface GlyphBox {
+  rect: Rect
+  line: number
+  start: number
+  end: number
+}
+
+export interface TextLayout {
+  width: number
+  height: number
+  baseline: number
+  ascent: number
+  descent: number
+  truncated: boolean
+  lines: Array<TextLine>
+  glyphs: Array<GlyphBox>
+}
+
 export interface Border {
   top: BorderSide
   right: BorderSide
   bottom: BorderSide
   left: BorderSide
+  image: (BorderImage) | undefined
 }
 
 export interface BorderSide {
@@ -482,5 +1078,63 @@ export interface BorderSide {
 
 export enum BorderStyle {
   None = 'None',
-  Solid = 'Solid'
+  Solid = 'Solid',
+  Dashed = 'Dashed',
+  Dotted = 'Dotted',
+  Double = 'Double',
+  Groove = 'Groove',
+  Ridge = 'Ridge',
+  Inset = 'Inset',
+  Outset = 'Outset'
+}
+
+export interface BorderImage {
+  source: BorderImageSource
+  slice: BorderImageSlice
+  fill: boolean
+  repeat: BorderImageRepeat
+}
+
+export type BorderImageSource =
+  | { tag: 'Image'; value: Image }
+  | { tag: 'LinearGradient'; value: LinearGradient }
+  | { tag: 'RadialGradient'; value: RadialGradient }
+
+export module BorderImageSource {
+  export const Image = (value: Image): BorderImageSource => ({
+    tag: 'Image',
+    value
+  })
+
+  export const LinearGradient = (value: LinearGradient): BorderImageSource => ({
+    tag: 'LinearGradient',
+    value
+  })
+
+  export const RadialGradient = (value: RadialGradient): BorderImageSource => ({
+    tag: 'RadialGradient',
+    value
+  })
+}
+
+export interface BorderImageSlice {
+  0: number
+  1: number
+  2: number
+  3: number
+  length: 4
+}
+
+export const BorderImageSlice = (
+  p0: number,
+  p1: number,
+  p2: number,
+  p3: number
+): BorderImageSlice => [p0, p1, p2, p3]
+
+export enum BorderImageRepeat {
+  Stretch = 'Stretch',
+  Repeat = 'Repeat',
+  Round = 'Round',
+  Space = 'Space'
 }
@@ -11,10 +11,16 @@ export {
   Image,
   JustifyContent,
   Text,
+  TextLayout,
+  SurfaceLayout,
   BorderRadius,
   Size,
   BorderSide,
-  BoxShadow
+  BoxShadow,
+  Background,
+  AnimatedProperty,
+  AnimatedValue,
+  Easing
 } from './generated'
 
 export { APP, App } from './App'
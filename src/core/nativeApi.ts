@@ -15,7 +15,9 @@ const lib = ffi.Library(
     send: [
       'void',
       [ref.refType(ref.types.void), 'int', ref.refType(ref.types.void)]
-    ]
+    ],
+    // copy the result which didn't fit (see FfiResult.Pending)
+    take_result: ['void', [ref.refType(ref.types.void)]]
   }
 )
 
@@ -26,6 +28,7 @@ let sink: Sink = {
   pos: 0
 }
 
+// has to be the same as in ffi.rs (bigger results are pending, see send())
 const resBuf = Buffer.alloc(1024, 0)
 
 export function send(msg: FfiMsg) {
@@ -47,6 +50,14 @@ export function send(msg: FfiMsg) {
 
   const res: FfiResult = readFfiResult({ arr: resBuf, pos: 0 })
 
+  // too big for resBuf
+  if (res.tag === 'Pending') {
+    const buf = Buffer.alloc(res.value, 0)
+    lib.take_result(buf)
+
+    return readFfiResult({ arr: buf, pos: 0 })
+  }
+
   // console.log(res)
   return res
 }
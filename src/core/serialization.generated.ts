@@ -3,7 +3,26 @@ import {
   WindowId,
   UpdateSceneMsg,
   FfiMsg_UpdateScene,
+  SurfaceId,
+  AnimatedProperty,
+  AnimatedValue,
+  Transform,
+  Color,
+  Vector2f,
+  Easing,
+  FfiMsg_Animate,
+  FfiMsg_TextOffsetAt,
+  FfiMsg_CaretRect,
+  Text,
+  FfiMsg_MeasureText,
+  FfiMsg_GetTextLayout,
+  FfiMsg_GetLayout,
   FfiResult,
+  Rect,
+  TextLayout,
+  TextLine,
+  GlyphBox,
+  SurfaceLayout,
   Event,
   WindowEvent,
   Event_WindowEvent,
@@ -11,7 +30,9 @@ import {
   WindowEvent_MouseDown,
   WindowEvent_MouseUp,
   WindowEvent_Scroll,
-  SurfaceId,
+  WindowEvent_AnimationEnd,
+  WindowEvent_TextChanged,
+  WindowEvent_LayoutChanged,
   UpdateSceneMsg_AppendChild,
   UpdateSceneMsg_InsertBefore,
   UpdateSceneMsg_RemoveChild,
@@ -29,25 +50,64 @@ import {
   UpdateSceneMsg_SetPadding,
   UpdateSceneMsg_SetMargin,
   BoxShadow,
-  UpdateSceneMsg_SetBoxShadow,
-  Color,
+  UpdateSceneMsg_SetBoxShadows,
   UpdateSceneMsg_SetBackgroundColor,
   Image,
   UpdateSceneMsg_SetImage,
-  Text,
   UpdateSceneMsg_SetText,
   Border,
   UpdateSceneMsg_SetBorder,
+  Svg,
+  UpdateSceneMsg_SetSvg,
+  UpdateSceneMsg_SetOpacity,
+  UpdateSceneMsg_SetZIndex,
+  UpdateSceneMsg_SetTransform,
+  Background,
+  UpdateSceneMsg_SetBackground,
+  TextInput,
+  UpdateSceneMsg_SetTextInput,
+  UpdateSceneMsg_ObserveLayout,
+  Position,
+  UpdateSceneMsg_SetPosition,
+  UpdateSceneMsg_SetMinSize,
+  UpdateSceneMsg_SetMaxSize,
+  UpdateSceneMsg_SetAspectRatio,
+  Display,
+  UpdateSceneMsg_SetDisplay,
+  Visibility,
+  UpdateSceneMsg_SetVisibility,
   FlexDirection,
   FlexWrap,
   FlexAlign,
   JustifyContent,
+  Direction,
   Dimension,
-  Rect,
-  Vector2f,
+  PositionType,
   TextAlign,
+  Ellipsize,
+  WhiteSpace,
+  WordBreak,
+  FontStyle,
+  TextSpan,
+  TextDecorationStyle,
+  TextDecoration,
+  TextShadow,
+  FontFeature,
+  FontVariation,
   BorderSide,
-  BorderStyle
+  BorderStyle,
+  BorderImage,
+  BorderImageSource,
+  BorderImageSlice,
+  BorderImageRepeat,
+  Matrix,
+  TransformOp,
+  TransformOrigin,
+  CornerRadius,
+  LinearGradient,
+  RadialGradient,
+  ConicGradient,
+  ColorStop
 } from './generated'
 
 import {
@@ -60,6 +120,7 @@ import {
   write_u8,
   write_f32,
   write_str,
+  write_i32,
   Sink,
   Serializer
 } from 'ts-binary'
@@ -70,13 +131,25 @@ const writeVecUpdateSceneMsg = (sink: Sink, val: Array<UpdateSceneMsg>): Sink =>
 const writeVecEvent = (sink: Sink, val: Array<Event>): Sink =>
   write_seq(sink, val, writeEvent)
 
+const writeVecTextLine = (sink: Sink, val: Array<TextLine>): Sink =>
+  write_seq(sink, val, writeTextLine)
+
+const writeVecGlyphBox = (sink: Sink, val: Array<GlyphBox>): Sink =>
+  write_seq(sink, val, writeGlyphBox)
+
+const writeVecSurfaceId = (sink: Sink, val: Array<SurfaceId>): Sink =>
+  write_seq(sink, val, writeSurfaceId)
+
+const writeVecSurfaceLayout = (sink: Sink, val: Array<SurfaceLayout>): Sink =>
+  write_seq(sink, val, writeSurfaceLayout)
+
 const writeOptBorderRadius = (
   sink: Sink,
   val: (BorderRadius) | undefined
 ): Sink => write_opt(sink, val, writeBorderRadius)
 
-const writeOptBoxShadow = (sink: Sink, val: (BoxShadow) | undefined): Sink =>
-  write_opt(sink, val, writeBoxShadow)
+const writeVecBoxShadow = (sink: Sink, val: Array<BoxShadow>): Sink =>
+  write_seq(sink, val, writeBoxShadow)
 
 const writeOptColor = (sink: Sink, val: (Color) | undefined): Sink =>
   write_opt(sink, val, writeColor)
@@ -90,6 +163,58 @@ const writeOptText = (sink: Sink, val: (Text) | undefined): Sink =>
 const writeOptBorder = (sink: Sink, val: (Border) | undefined): Sink =>
   write_opt(sink, val, writeBorder)
 
+const writeOptSvg = (sink: Sink, val: (Svg) | undefined): Sink =>
+  write_opt(sink, val, writeSvg)
+
+const writeOptTransform = (sink: Sink, val: (Transform) | undefined): Sink =>
+  write_opt(sink, val, writeTransform)
+
+const writeVecTransformOp = (sink: Sink, val: Array<TransformOp>): Sink =>
+  write_seq(sink, val, writeTransformOp)
+
+const writeOptBackground = (sink: Sink, val: (Background) | undefined): Sink =>
+  write_opt(sink, val, writeBackground)
+
+const writeOptTextInput = (sink: Sink, val: (TextInput) | undefined): Sink =>
+  write_opt(sink, val, writeTextInput)
+
+const writeOptVisibility = (sink: Sink, val: (Visibility) | undefined): Sink =>
+  write_opt(sink, val, writeVisibility)
+
+const writeVecColorStop = (sink: Sink, val: Array<ColorStop>): Sink =>
+  write_seq(sink, val, writeColorStop)
+
+const writeOptBorderImage = (
+  sink: Sink,
+  val: (BorderImage) | undefined
+): Sink => write_opt(sink, val, writeBorderImage)
+
+const writeOptU32 = (sink: Sink, val: (number) | undefined): Sink =>
+  write_opt(sink, val, write_u32)
+
+const writeVecTextSpan = (sink: Sink, val: Array<TextSpan>): Sink =>
+  write_seq(sink, val, writeTextSpan)
+
+const writeOptF32 = (sink: Sink, val: (number) | undefined): Sink =>
+  write_opt(sink, val, write_f32)
+
+const writeOptFontStyle = (sink: Sink, val: (FontStyle) | undefined): Sink =>
+  write_opt(sink, val, writeFontStyle)
+
+const writeOptTextDecoration = (
+  sink: Sink,
+  val: (TextDecoration) | undefined
+): Sink => write_opt(sink, val, writeTextDecoration)
+
+const writeVecTextShadow = (sink: Sink, val: Array<TextShadow>): Sink =>
+  write_seq(sink, val, writeTextShadow)
+
+const writeVecFontFeature = (sink: Sink, val: Array<FontFeature>): Sink =>
+  write_seq(sink, val, writeFontFeature)
+
+const writeVecFontVariation = (sink: Sink, val: Array<FontVariation>): Sink =>
+  write_seq(sink, val, writeFontVariation)
+
 export const writeFfiMsg = (sink: Sink, val: FfiMsg): Sink => {
   switch (val.tag) {
     case 'GetEvents':
@@ -98,6 +223,18 @@ export const writeFfiMsg = (sink: Sink, val: FfiMsg): Sink => {
       return write_u32(sink, 1)
     case 'UpdateScene':
       return writeFfiMsg_UpdateScene(write_u32(sink, 2), val.value)
+    case 'Animate':
+      return writeFfiMsg_Animate(write_u32(sink, 3), val.value)
+    case 'TextOffsetAt':
+      return writeFfiMsg_TextOffsetAt(write_u32(sink, 4), val.value)
+    case 'CaretRect':
+      return writeFfiMsg_CaretRect(write_u32(sink, 5), val.value)
+    case 'MeasureText':
+      return writeFfiMsg_MeasureText(write_u32(sink, 6), val.value)
+    case 'GetTextLayout':
+      return writeFfiMsg_GetTextLayout(write_u32(sink, 7), val.value)
+    case 'GetLayout':
+      return writeFfiMsg_GetLayout(write_u32(sink, 8), val.value)
   }
 }
 
@@ -106,6 +243,73 @@ const writeFfiMsg_UpdateScene = (
   { window, msgs }: FfiMsg_UpdateScene
 ): Sink => writeVecUpdateSceneMsg(writeWindowId(sink, window), msgs)
 
+const writeFfiMsg_Animate = (
+  sink: Sink,
+  {
+    window,
+    surface,
+    property,
+    from,
+    to,
+    duration,
+    easing,
+    delay,
+    iterations
+  }: FfiMsg_Animate
+): Sink =>
+  write_u32(
+    write_f32(
+      writeEasing(
+        write_f32(
+          writeAnimatedValue(
+            writeAnimatedValue(
+              writeAnimatedProperty(
+                writeSurfaceId(writeWindowId(sink, window), surface),
+                property
+              ),
+              from
+            ),
+            to
+          ),
+          duration
+        ),
+        easing
+      ),
+      delay
+    ),
+    iterations
+  )
+
+const writeFfiMsg_TextOffsetAt = (
+  sink: Sink,
+  { window, surface, x, y }: FfiMsg_TextOffsetAt
+): Sink =>
+  write_f32(
+    write_f32(writeSurfaceId(writeWindowId(sink, window), surface), x),
+    y
+  )
+
+const writeFfiMsg_CaretRect = (
+  sink: Sink,
+  { window, surface, offset }: FfiMsg_CaretRect
+): Sink =>
+  write_u32(writeSurfaceId(writeWindowId(sink, window), surface), offset)
+
+const writeFfiMsg_MeasureText = (
+  sink: Sink,
+  { text, maxWidth }: FfiMsg_MeasureText
+): Sink => writeOptF32(writeText(sink, text), maxWidth)
+
+const writeFfiMsg_GetTextLayout = (
+  sink: Sink,
+  { window, surface }: FfiMsg_GetTextLayout
+): Sink => writeSurfaceId(writeWindowId(sink, window), surface)
+
+const writeFfiMsg_GetLayout = (
+  sink: Sink,
+  { window, surfaces }: FfiMsg_GetLayout
+): Sink => writeVecSurfaceId(writeWindowId(sink, window), surfaces)
+
 export const writeFfiResult = (sink: Sink, val: FfiResult): Sink => {
   switch (val.tag) {
     case 'Nothing':
@@ -114,6 +318,16 @@ export const writeFfiResult = (sink: Sink, val: FfiResult): Sink => {
       return writeVecEvent(write_u32(sink, 1), val.value)
     case 'WindowId':
       return writeWindowId(write_u32(sink, 2), val.value)
+    case 'TextOffset':
+      return write_u32(write_u32(sink, 3), val.value)
+    case 'Rect':
+      return writeRect(write_u32(sink, 4), val.value)
+    case 'TextLayout':
+      return writeTextLayout(write_u32(sink, 5), val.value)
+    case 'Layouts':
+      return writeVecSurfaceLayout(write_u32(sink, 6), val.value)
+    case 'Pending':
+      return write_u32(write_u32(sink, 7), val.value)
   }
 }
 
@@ -155,29 +369,50 @@ export const writeWindowEvent = (sink: Sink, val: WindowEvent): Sink => {
       return write_u32(sink, 10)
     case 'Unknown':
       return write_u32(sink, 11)
+    case 'AnimationEnd':
+      return writeWindowEvent_AnimationEnd(write_u32(sink, 12), val.value)
+    case 'TextChanged':
+      return writeWindowEvent_TextChanged(write_u32(sink, 13), val.value)
+    case 'LayoutChanged':
+      return writeWindowEvent_LayoutChanged(write_u32(sink, 14), val.value)
   }
 }
 
 const writeWindowEvent_MouseMove = (
   sink: Sink,
-  { target }: WindowEvent_MouseMove
-): Sink => write_u64(sink, target)
+  { target, link }: WindowEvent_MouseMove
+): Sink => writeOptU32(write_u64(sink, target), link)
 
 const writeWindowEvent_MouseDown = (
   sink: Sink,
-  { target }: WindowEvent_MouseDown
-): Sink => write_u64(sink, target)
+  { target, link }: WindowEvent_MouseDown
+): Sink => writeOptU32(write_u64(sink, target), link)
 
 const writeWindowEvent_MouseUp = (
   sink: Sink,
-  { target }: WindowEvent_MouseUp
-): Sink => write_u64(sink, target)
+  { target, link }: WindowEvent_MouseUp
+): Sink => writeOptU32(write_u64(sink, target), link)
 
 const writeWindowEvent_Scroll = (
   sink: Sink,
   { target }: WindowEvent_Scroll
 ): Sink => write_u64(sink, target)
 
+const writeWindowEvent_AnimationEnd = (
+  sink: Sink,
+  { target, property }: WindowEvent_AnimationEnd
+): Sink => writeAnimatedProperty(write_u64(sink, target), property)
+
+const writeWindowEvent_TextChanged = (
+  sink: Sink,
+  { target, value }: WindowEvent_TextChanged
+): Sink => write_str(write_u64(sink, target), value)
+
+const writeWindowEvent_LayoutChanged = (
+  sink: Sink,
+  { target, rect }: WindowEvent_LayoutChanged
+): Sink => writeRect(write_u64(sink, target), rect)
+
 export const writeUpdateSceneMsg = (sink: Sink, val: UpdateSceneMsg): Sink => {
   switch (val.tag) {
     case 'Alloc':
@@ -202,8 +437,8 @@ export const writeUpdateSceneMsg = (sink: Sink, val: UpdateSceneMsg): Sink => {
       return writeUpdateSceneMsg_SetPadding(write_u32(sink, 9), val.value)
     case 'SetMargin':
       return writeUpdateSceneMsg_SetMargin(write_u32(sink, 10), val.value)
-    case 'SetBoxShadow':
-      return writeUpdateSceneMsg_SetBoxShadow(write_u32(sink, 11), val.value)
+    case 'SetBoxShadows':
+      return writeUpdateSceneMsg_SetBoxShadows(write_u32(sink, 11), val.value)
     case 'SetBackgroundColor':
       return writeUpdateSceneMsg_SetBackgroundColor(
         write_u32(sink, 12),
@@ -215,6 +450,32 @@ export const writeUpdateSceneMsg = (sink: Sink, val: UpdateSceneMsg): Sink => {
       return writeUpdateSceneMsg_SetText(write_u32(sink, 14), val.value)
     case 'SetBorder':
       return writeUpdateSceneMsg_SetBorder(write_u32(sink, 15), val.value)
+    case 'SetSvg':
+      return writeUpdateSceneMsg_SetSvg(write_u32(sink, 16), val.value)
+    case 'SetOpacity':
+      return writeUpdateSceneMsg_SetOpacity(write_u32(sink, 17), val.value)
+    case 'SetZIndex':
+      return writeUpdateSceneMsg_SetZIndex(write_u32(sink, 18), val.value)
+    case 'SetTransform':
+      return writeUpdateSceneMsg_SetTransform(write_u32(sink, 19), val.value)
+    case 'SetBackground':
+      return writeUpdateSceneMsg_SetBackground(write_u32(sink, 20), val.value)
+    case 'SetTextInput':
+      return writeUpdateSceneMsg_SetTextInput(write_u32(sink, 21), val.value)
+    case 'ObserveLayout':
+      return writeUpdateSceneMsg_ObserveLayout(write_u32(sink, 22), val.value)
+    case 'SetPosition':
+      return writeUpdateSceneMsg_SetPosition(write_u32(sink, 23), val.value)
+    case 'SetMinSize':
+      return writeUpdateSceneMsg_SetMinSize(write_u32(sink, 24), val.value)
+    case 'SetMaxSize':
+      return writeUpdateSceneMsg_SetMaxSize(write_u32(sink, 25), val.value)
+    case 'SetAspectRatio':
+      return writeUpdateSceneMsg_SetAspectRatio(write_u32(sink, 26), val.value)
+    case 'SetDisplay':
+      return writeUpdateSceneMsg_SetDisplay(write_u32(sink, 27), val.value)
+    case 'SetVisibility':
+      return writeUpdateSceneMsg_SetVisibility(write_u32(sink, 28), val.value)
   }
 }
 
@@ -269,10 +530,10 @@ const writeUpdateSceneMsg_SetMargin = (
   { surface, margin }: UpdateSceneMsg_SetMargin
 ): Sink => writeDimensions(writeSurfaceId(sink, surface), margin)
 
-const writeUpdateSceneMsg_SetBoxShadow = (
+const writeUpdateSceneMsg_SetBoxShadows = (
   sink: Sink,
-  { surface, boxShadow }: UpdateSceneMsg_SetBoxShadow
-): Sink => writeOptBoxShadow(writeSurfaceId(sink, surface), boxShadow)
+  { surface, boxShadows }: UpdateSceneMsg_SetBoxShadows
+): Sink => writeVecBoxShadow(writeSurfaceId(sink, surface), boxShadows)
 
 const writeUpdateSceneMsg_SetBackgroundColor = (
   sink: Sink,
@@ -294,10 +555,110 @@ const writeUpdateSceneMsg_SetBorder = (
   { surface, border }: UpdateSceneMsg_SetBorder
 ): Sink => writeOptBorder(writeSurfaceId(sink, surface), border)
 
+const writeUpdateSceneMsg_SetSvg = (
+  sink: Sink,
+  { surface, svg }: UpdateSceneMsg_SetSvg
+): Sink => writeOptSvg(writeSurfaceId(sink, surface), svg)
+
+const writeUpdateSceneMsg_SetOpacity = (
+  sink: Sink,
+  { surface, opacity }: UpdateSceneMsg_SetOpacity
+): Sink => write_f32(writeSurfaceId(sink, surface), opacity)
+
+const writeUpdateSceneMsg_SetZIndex = (
+  sink: Sink,
+  { surface, zIndex }: UpdateSceneMsg_SetZIndex
+): Sink => write_i32(writeSurfaceId(sink, surface), zIndex)
+
+const writeUpdateSceneMsg_SetTransform = (
+  sink: Sink,
+  { surface, transform }: UpdateSceneMsg_SetTransform
+): Sink => writeOptTransform(writeSurfaceId(sink, surface), transform)
+
+const writeUpdateSceneMsg_SetBackground = (
+  sink: Sink,
+  { surface, background }: UpdateSceneMsg_SetBackground
+): Sink => writeOptBackground(writeSurfaceId(sink, surface), background)
+
+const writeUpdateSceneMsg_SetTextInput = (
+  sink: Sink,
+  { surface, textInput }: UpdateSceneMsg_SetTextInput
+): Sink => writeOptTextInput(writeSurfaceId(sink, surface), textInput)
+
+const writeUpdateSceneMsg_ObserveLayout = (
+  sink: Sink,
+  { surface, observe }: UpdateSceneMsg_ObserveLayout
+): Sink => write_bool(writeSurfaceId(sink, surface), observe)
+
+const writeUpdateSceneMsg_SetPosition = (
+  sink: Sink,
+  { surface, position }: UpdateSceneMsg_SetPosition
+): Sink => writePosition(writeSurfaceId(sink, surface), position)
+
+const writeUpdateSceneMsg_SetMinSize = (
+  sink: Sink,
+  { surface, size }: UpdateSceneMsg_SetMinSize
+): Sink => writeSize(writeSurfaceId(sink, surface), size)
+
+const writeUpdateSceneMsg_SetMaxSize = (
+  sink: Sink,
+  { surface, size }: UpdateSceneMsg_SetMaxSize
+): Sink => writeSize(writeSurfaceId(sink, surface), size)
+
+const writeUpdateSceneMsg_SetAspectRatio = (
+  sink: Sink,
+  { surface, aspectRatio }: UpdateSceneMsg_SetAspectRatio
+): Sink => writeOptF32(writeSurfaceId(sink, surface), aspectRatio)
+
+const writeUpdateSceneMsg_SetDisplay = (
+  sink: Sink,
+  { surface, display }: UpdateSceneMsg_SetDisplay
+): Sink => writeDisplay(writeSurfaceId(sink, surface), display)
+
+const writeUpdateSceneMsg_SetVisibility = (
+  sink: Sink,
+  { surface, visibility }: UpdateSceneMsg_SetVisibility
+): Sink => writeOptVisibility(writeSurfaceId(sink, surface), visibility)
+
 export const writeWindowId: Serializer<WindowId> = write_u16
 
 export const writeSurfaceId: Serializer<SurfaceId> = write_u64
 
+const AnimatedPropertyMap: { [key: string]: number } = {
+  Opacity: 0,
+  Transform: 1,
+  BackgroundColor: 2,
+  ScrollOffset: 3
+}
+
+export const writeAnimatedProperty = (
+  sink: Sink,
+  val: AnimatedProperty
+): Sink => write_u32(sink, AnimatedPropertyMap[val])
+
+export const writeAnimatedValue = (sink: Sink, val: AnimatedValue): Sink => {
+  switch (val.tag) {
+    case 'Opacity':
+      return write_f32(write_u32(sink, 0), val.value)
+    case 'Transform':
+      return writeTransform(write_u32(sink, 1), val.value)
+    case 'BackgroundColor':
+      return writeColor(write_u32(sink, 2), val.value)
+    case 'ScrollOffset':
+      return writeVector2f(write_u32(sink, 3), val.value)
+  }
+}
+
+const EasingMap: { [key: string]: number } = {
+  Linear: 0,
+  EaseIn: 1,
+  EaseOut: 2,
+  EaseInOut: 3
+}
+
+export const writeEasing = (sink: Sink, val: Easing): Sink =>
+  write_u32(sink, EasingMap[val])
+
 export const writeColor = (sink: Sink, val: Color): Sink =>
   write_u8(write_u8(write_u8(write_u8(sink, val[0]), val[1]), val[2]), val[3])
 
@@ -346,6 +707,15 @@ const JustifyContentMap: { [key: string]: number } = {
 export const writeJustifyContent = (sink: Sink, val: JustifyContent): Sink =>
   write_u32(sink, JustifyContentMap[val])
 
+const DirectionMap: { [key: string]: number } = {
+  Inherit: 0,
+  Ltr: 1,
+  Rtl: 2
+}
+
+export const writeDirection = (sink: Sink, val: Direction): Sink =>
+  write_u32(sink, DirectionMap[val])
+
 export const writeFlow = (
   sink: Sink,
   {
@@ -354,21 +724,25 @@ export const writeFlow = (
     alignContent,
     alignItems,
     alignSelf,
-    justifyContent
+    justifyContent,
+    direction
   }: Flow
 ): Sink =>
-  writeJustifyContent(
-    writeFlexAlign(
+  writeDirection(
+    writeJustifyContent(
       writeFlexAlign(
         writeFlexAlign(
-          writeFlexWrap(writeFlexDirection(sink, flexDirection), flexWrap),
-          alignContent
+          writeFlexAlign(
+            writeFlexWrap(writeFlexDirection(sink, flexDirection), flexWrap),
+            alignContent
+          ),
+          alignItems
         ),
-        alignItems
+        alignSelf
       ),
-      alignSelf
+      justifyContent
     ),
-    justifyContent
+    direction
   )
 
 export const writeFlex = (
@@ -397,6 +771,42 @@ const OverflowMap: { [key: string]: number } = {
 export const writeOverflow = (sink: Sink, val: Overflow): Sink =>
   write_u32(sink, OverflowMap[val])
 
+const PositionTypeMap: { [key: string]: number } = {
+  Relative: 0,
+  Absolute: 1
+}
+
+export const writePositionType = (sink: Sink, val: PositionType): Sink =>
+  write_u32(sink, PositionTypeMap[val])
+
+export const writePosition = (
+  sink: Sink,
+  { kind, top, right, bottom, left }: Position
+): Sink =>
+  writeDimension(
+    writeDimension(
+      writeDimension(writeDimension(writePositionType(sink, kind), top), right),
+      bottom
+    ),
+    left
+  )
+
+const DisplayMap: { [key: string]: number } = {
+  Flex: 0,
+  None: 1
+}
+
+export const writeDisplay = (sink: Sink, val: Display): Sink =>
+  write_u32(sink, DisplayMap[val])
+
+const VisibilityMap: { [key: string]: number } = {
+  Visible: 0,
+  Hidden: 1
+}
+
+export const writeVisibility = (sink: Sink, val: Visibility): Sink =>
+  write_u32(sink, VisibilityMap[val])
+
 export const writeSize = (sink: Sink, val: Size): Sink =>
   writeDimension(writeDimension(sink, val[0]), val[1])
 
@@ -406,6 +816,11 @@ export const writeRect = (sink: Sink, val: Rect): Sink =>
     val[3]
   )
 
+export const writeSurfaceLayout = (
+  sink: Sink,
+  { rect, absolute }: SurfaceLayout
+): Sink => writeRect(writeRect(sink, rect), absolute)
+
 export const writeDimensions = (sink: Sink, val: Dimensions): Sink =>
   writeDimension(
     writeDimension(
@@ -418,48 +833,358 @@ export const writeDimensions = (sink: Sink, val: Dimensions): Sink =>
 export const writeVector2f = (sink: Sink, val: Vector2f): Sink =>
   write_f32(write_f32(sink, val[0]), val[1])
 
+export const writeCornerRadius = (sink: Sink, val: CornerRadius): Sink =>
+  writeDimension(writeDimension(sink, val[0]), val[1])
+
 export const writeBorderRadius = (sink: Sink, val: BorderRadius): Sink =>
-  write_f32(
-    write_f32(write_f32(write_f32(sink, val[0]), val[1]), val[2]),
+  writeCornerRadius(
+    writeCornerRadius(
+      writeCornerRadius(writeCornerRadius(sink, val[0]), val[1]),
+      val[2]
+    ),
     val[3]
   )
 
 export const writeBoxShadow = (
   sink: Sink,
-  { color, offset, blur, spread }: BoxShadow
+  { color, offset, blur, spread, inset }: BoxShadow
 ): Sink =>
+  write_bool(
+    write_f32(
+      write_f32(writeVector2f(writeColor(sink, color), offset), blur),
+      spread
+    ),
+    inset
+  )
+
+export const writeMatrix = (sink: Sink, val: Matrix): Sink =>
   write_f32(
-    write_f32(writeVector2f(writeColor(sink, color), offset), blur),
-    spread
+    write_f32(
+      write_f32(
+        write_f32(write_f32(write_f32(sink, val[0]), val[1]), val[2]),
+        val[3]
+      ),
+      val[4]
+    ),
+    val[5]
   )
 
+export const writeTransformOp = (sink: Sink, val: TransformOp): Sink => {
+  switch (val.tag) {
+    case 'Matrix':
+      return writeMatrix(write_u32(sink, 0), val.value)
+    case 'Translate':
+      return writeVector2f(write_u32(sink, 1), val.value)
+    case 'Scale':
+      return writeVector2f(write_u32(sink, 2), val.value)
+    case 'Rotate':
+      return write_f32(write_u32(sink, 3), val.value)
+    case 'Skew':
+      return writeVector2f(write_u32(sink, 4), val.value)
+  }
+}
+
+export const writeTransformOrigin = (sink: Sink, val: TransformOrigin): Sink =>
+  writeDimension(writeDimension(sink, val[0]), val[1])
+
+export const writeTransform = (sink: Sink, { ops, origin }: Transform): Sink =>
+  writeTransformOrigin(writeVecTransformOp(sink, ops), origin)
+
+export const writeColorStop = (sink: Sink, { offset, color }: ColorStop): Sink =>
+  writeColor(write_f32(sink, offset), color)
+
+export const writeLinearGradient = (
+  sink: Sink,
+  { angle, stops }: LinearGradient
+): Sink => writeVecColorStop(write_f32(sink, angle), stops)
+
+export const writeRadialGradient = (
+  sink: Sink,
+  { center, radius, stops }: RadialGradient
+): Sink =>
+  writeVecColorStop(writeVector2f(writeVector2f(sink, center), radius), stops)
+
+export const writeConicGradient = (
+  sink: Sink,
+  { center, angle, stops }: ConicGradient
+): Sink =>
+  writeVecColorStop(write_f32(writeVector2f(sink, center), angle), stops)
+
+export const writeBackground = (sink: Sink, val: Background): Sink => {
+  switch (val.tag) {
+    case 'LinearGradient':
+      return writeLinearGradient(write_u32(sink, 0), val.value)
+    case 'RadialGradient':
+      return writeRadialGradient(write_u32(sink, 1), val.value)
+    case 'ConicGradient':
+      return writeConicGradient(write_u32(sink, 2), val.value)
+  }
+}
+
 export const writeImage = (sink: Sink, { url }: Image): Sink =>
   write_str(sink, url)
 
-const TextAlignMap: { [key: string]: number } = { Left: 0, Center: 1, Right: 2 }
+export const writeSvg = (sink: Sink, val: Svg): Sink => {
+  switch (val.tag) {
+    case 'Source':
+      return write_str(write_u32(sink, 0), val.value)
+    case 'Path':
+      return write_str(write_u32(sink, 1), val.value)
+  }
+}
+
+const TextAlignMap: { [key: string]: number } = {
+  Left: 0,
+  Center: 1,
+  Right: 2,
+  Justify: 3
+}
 
 export const writeTextAlign = (sink: Sink, val: TextAlign): Sink =>
   write_u32(sink, TextAlignMap[val])
 
+const EllipsizeMap: { [key: string]: number } = {
+  None: 0,
+  Start: 1,
+  Middle: 2,
+  End: 3
+}
+
+export const writeEllipsize = (sink: Sink, val: Ellipsize): Sink =>
+  write_u32(sink, EllipsizeMap[val])
+
+const WhiteSpaceMap: { [key: string]: number } = {
+  Normal: 0,
+  NoWrap: 1,
+  Pre: 2,
+  PreWrap: 3,
+  PreLine: 4
+}
+
+export const writeWhiteSpace = (sink: Sink, val: WhiteSpace): Sink =>
+  write_u32(sink, WhiteSpaceMap[val])
+
+const WordBreakMap: { [key: string]: number } = {
+  Normal: 0,
+  BreakAll: 1,
+  BreakWord: 2
+}
+
+export const writeWordBreak = (sink: Sink, val: WordBreak): Sink =>
+  write_u32(sink, WordBreakMap[val])
+
+const FontStyleMap: { [key: string]: number } = { Normal: 0, Italic: 1 }
+
+export const writeFontStyle = (sink: Sink, val: FontStyle): Sink =>
+  write_u32(sink, FontStyleMap[val])
+
+export const writeTextSpan = (
+  sink: Sink,
+  {
+    start,
+    end,
+    color,
+    fontSize,
+    fontWeight,
+    fontStyle,
+    underline,
+    link
+  }: TextSpan
+): Sink =>
+  writeOptU32(
+    write_bool(
+      writeOptFontStyle(
+        writeOptU32(
+          writeOptF32(
+            writeOptColor(write_u32(write_u32(sink, start), end), color),
+            fontSize
+          ),
+          fontWeight
+        ),
+        fontStyle
+      ),
+      underline
+    ),
+    link
+  )
+
+const TextDecorationStyleMap: { [key: string]: number } = {
+  Solid: 0,
+  Double: 1,
+  Dotted: 2,
+  Dashed: 3,
+  Wavy: 4
+}
+
+export const writeTextDecorationStyle = (
+  sink: Sink,
+  val: TextDecorationStyle
+): Sink => write_u32(sink, TextDecorationStyleMap[val])
+
+export const writeTextDecoration = (
+  sink: Sink,
+  { underline, lineThrough, overline, color, style }: TextDecoration
+): Sink =>
+  writeTextDecorationStyle(
+    writeOptColor(
+      write_bool(
+        write_bool(write_bool(sink, underline), lineThrough),
+        overline
+      ),
+      color
+    ),
+    style
+  )
+
+export const writeTextShadow = (
+  sink: Sink,
+  { offset, blur, color }: TextShadow
+): Sink => writeColor(write_f32(writeVector2f(sink, offset), blur), color)
+
+export const writeFontFeature = (
+  sink: Sink,
+  { tag, value }: FontFeature
+): Sink => write_u32(write_str(sink, tag), value)
+
+export const writeFontVariation = (
+  sink: Sink,
+  { tag, value }: FontVariation
+): Sink => write_f32(write_str(sink, tag), value)
+
 export const writeText = (
   sink: Sink,
-  { color, fontSize, lineHeight, align, text }: Text
+  {
+    color,
+    fontSize,
+    lineHeight,
+    align,
+    numberOfLines,
+    ellipsize,
+    whiteSpace,
+    wordBreak,
+    spans,
+    decoration,
+    shadows,
+    letterSpacing,
+    fontFeatures,
+    fontVariations,
+    direction,
+    text
+  }: Text
 ): Sink =>
   write_str(
-    writeTextAlign(
-      write_f32(write_f32(writeColor(sink, color), fontSize), lineHeight),
-      align
+    writeDirection(
+      writeVecFontVariation(
+        writeVecFontFeature(
+          write_f32(
+            writeVecTextShadow(
+              writeOptTextDecoration(
+                writeVecTextSpan(
+                  writeWordBreak(
+                    writeWhiteSpace(
+                      writeEllipsize(
+                        write_u32(
+                          writeTextAlign(
+                            write_f32(
+                              write_f32(writeColor(sink, color), fontSize),
+                              lineHeight
+                            ),
+                            align
+                          ),
+                          numberOfLines
+                        ),
+                        ellipsize
+                      ),
+                      whiteSpace
+                    ),
+                    wordBreak
+                  ),
+                  spans
+                ),
+                decoration
+              ),
+              shadows
+            ),
+            letterSpacing
+          ),
+          fontFeatures
+        ),
+        fontVariations
+      ),
+      direction
     ),
     text
   )
 
+export const writeTextInput = (
+  sink: Sink,
+  { value, placeholder, placeholderColor, secure, multiline }: TextInput
+): Sink =>
+  write_bool(
+    write_bool(
+      writeOptColor(
+        write_str(write_str(sink, value), placeholder),
+        placeholderColor
+      ),
+      secure
+    ),
+    multiline
+  )
+
+export const writeTextLine = (
+  sink: Sink,
+  { rect, start, baseline }: TextLine
+): Sink => write_f32(write_u64(writeRect(sink, rect), start), baseline)
+
+export const writeGlyphBox = (
+  sink: Sink,
+  { rect, line, start, end }: GlyphBox
+): Sink =>
+  write_u64(write_u64(write_u64(writeRect(sink, rect), line), start), end)
+
+export const writeTextLayout = (
+  sink: Sink,
+  {
+    width,
+    height,
+    baseline,
+    ascent,
+    descent,
+    truncated,
+    lines,
+    glyphs
+  }: TextLayout
+): Sink =>
+  writeVecGlyphBox(
+    writeVecTextLine(
+      write_bool(
+        write_f32(
+          write_f32(
+            write_f32(write_f32(write_f32(sink, width), height), baseline),
+            ascent
+          ),
+          descent
+        ),
+        truncated
+      ),
+      lines
+    ),
+    glyphs
+  )
+
 export const writeBorder = (
   sink: Sink,
-  { top, right, bottom, left }: Border
+  { top, right, bottom, left, image }: Border
 ): Sink =>
-  writeBorderSide(
-    writeBorderSide(writeBorderSide(writeBorderSide(sink, top), right), bottom),
-    left
+  writeOptBorderImage(
+    writeBorderSide(
+      writeBorderSide(
+        writeBorderSide(writeBorderSide(sink, top), right),
+        bottom
+      ),
+      left
+    ),
+    image
   )
 
 export const writeBorderSide = (
@@ -467,7 +1192,64 @@ export const writeBorderSide = (
   { width, style, color }: BorderSide
 ): Sink => writeColor(writeBorderStyle(write_f32(sink, width), style), color)
 
-const BorderStyleMap: { [key: string]: number } = { None: 0, Solid: 1 }
+const BorderStyleMap: { [key: string]: number } = {
+  None: 0,
+  Solid: 1,
+  Dashed: 2,
+  Dotted: 3,
+  Double: 4,
+  Groove: 5,
+  Ridge: 6,
+  Inset: 7,
+  Outset: 8
+}
 
 export const writeBorderStyle = (sink: Sink, val: BorderStyle): Sink =>
   write_u32(sink, BorderStyleMap[val])
+
+export const writeBorderImage = (
+  sink: Sink,
+  { source, slice, fill, repeat }: BorderImage
+): Sink =>
+  writeBorderImageRepeat(
+    write_bool(
+      writeBorderImageSlice(writeBorderImageSource(sink, source), slice),
+      fill
+    ),
+    repeat
+  )
+
+export const writeBorderImageSource = (
+  sink: Sink,
+  val: BorderImageSource
+): Sink => {
+  switch (val.tag) {
+    case 'Image':
+      return writeImage(write_u32(sink, 0), val.value)
+    case 'LinearGradient':
+      return writeLinearGradient(write_u32(sink, 1), val.value)
+    case 'RadialGradient':
+      return writeRadialGradient(write_u32(sink, 2), val.value)
+  }
+}
+
+export const writeBorderImageSlice = (
+  sink: Sink,
+  val: BorderImageSlice
+): Sink =>
+  write_u32(
+    write_u32(write_u32(write_u32(sink, val[0]), val[1]), val[2]),
+    val[3]
+  )
+
+const BorderImageRepeatMap: { [key: string]: number } = {
+  Stretch: 0,
+  Repeat: 1,
+  Round: 2,
+  Space: 3
+}
+
+export const writeBorderImageRepeat = (
+  sink: Sink,
+  val: BorderImageRepeat
+): Sink => write_u32(sink, BorderImageRepeatMap[val])
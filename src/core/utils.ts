@@ -3,14 +3,26 @@ import { Color } from '.';
 export const NOOP = () => undefined
 export const IDENTITY = v => v
 
-// TODO: rgb(), rgba()
+// #hex, rgb(), rgba() and basic named colors
 // https://docs.rs/crate/cssparser/0.25.3/source/src/color.rs
 export const parseColor = (str: string): Color => {
   if (str[0] === '#') {
     return parseHash(str.slice(1))
   }
 
-  throw new Error('only colors starting with # are supported')
+  const [, args] = str.match(/^rgba?\((.*)\)$/) || ([] as any)
+
+  if (args !== undefined) {
+    const [r, g, b, a = 1] = args.split(',').map(parseFloat)
+
+    return [r, g, b, Math.round(a * 255)]
+  }
+
+  if (str in NAMED_COLORS) {
+    return parseHash(NAMED_COLORS[str])
+  }
+
+  throw new Error(`unsupported color ${str}`)
 }
 
 export const parseHash = (str: string): Color => {
@@ -49,4 +61,25 @@ export const parseHash = (str: string): Color => {
 
 export const parseHex = (str: string) => parseInt(str, 16)
 
+const NAMED_COLORS = {
+  transparent: '0000',
+  black: '000',
+  silver: 'c0c0c0',
+  gray: '808080',
+  white: 'fff',
+  maroon: '800000',
+  red: 'f00',
+  purple: '800080',
+  fuchsia: 'f0f',
+  green: '008000',
+  lime: '0f0',
+  olive: '808000',
+  yellow: 'ff0',
+  navy: '000080',
+  blue: '00f',
+  teal: '008080',
+  aqua: '0ff',
+  orange: 'ffa500'
+}
+
 const COLOR_CACHE = new Map<string, Color>()
@@ -12,16 +12,31 @@ import {
   JustifyContent,
   FlexAlign,
   FlexWrap,
+  Direction,
   BorderStyle,
   Vector2f,
   Flex,
   Flow,
   BorderRadius,
+  CornerRadius,
   BoxShadow,
   Border,
   Color,
   Overflow,
-  Image
+  Position,
+  PositionType,
+  Display,
+  Visibility,
+  Image,
+  Transform,
+  TransformOp,
+  TransformOrigin,
+  Matrix,
+  Background,
+  ColorStop,
+  BorderImageSource,
+  BorderImageSlice,
+  BorderImageRepeat
 } from '../core/generated'
 import { parseColor } from '../core/utils'
 
@@ -74,6 +89,11 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
   const {
     width = 'auto',
     height = 'auto',
+    minWidth = 'auto',
+    minHeight = 'auto',
+    maxWidth = 'auto',
+    maxHeight = 'auto',
+    aspectRatio,
     flex = 0,
     flexDirection = 'column',
     padding = 0,
@@ -83,20 +103,39 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
     alignSelf = 'auto',
     justifyContent = 'flex-start',
     flexWrap = 'no-wrap',
+    direction = 'inherit',
     // FlexStyle contains 'scroll' too, but ImageStyle does not
     overflow = 'visible',
-
+    position = 'relative',
+    top = 'auto',
+    right = 'auto',
+    bottom = 'auto',
+    left = 'auto',
+    display = 'flex',
+    // inherited if it's not set
+    visibility,
+
+    opacity = 1,
+    zIndex = 0,
+    transform,
+    transformOrigin = ['50%', '50%'],
     shadowColor,
     //shadowOffset,
     //shadowOpacity,
     shadowRadius = 0,
     shadowSpread = 0,
+    boxShadow,
     backgroundColor,
+    background,
     backgroundImageUrl,
-    // TODO: BorderStyle
     borderRadius = 0,
     borderColor = '#000000',
     borderWidth = 0,
+    borderStyle = 'solid',
+    borderImageSource,
+    borderImageSlice = 0,
+    borderImageRepeat = 'stretch',
+    borderImageFill = false,
 
     ...rest
   } = style
@@ -145,6 +184,9 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
   return {
     overflow: Overflow[OVERFLOW[overflow]],
     size: Size(parseDimension(width), parseDimension(height)),
+    minSize: Size(parseDimension(minWidth), parseDimension(minHeight)),
+    maxSize: Size(parseDimension(maxWidth), parseDimension(maxHeight)),
+    aspectRatio,
     flex: {
       flexGrow,
       flexShrink,
@@ -156,7 +198,8 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
       alignContent: FlexAlign[FLEX_ALIGN[alignContent]],
       alignItems: FlexAlign[FLEX_ALIGN[alignItems]],
       alignSelf: FlexAlign[FLEX_ALIGN[alignSelf]],
-      justifyContent: JustifyContent[JUSTIFY_CONTENT[justifyContent]]
+      justifyContent: JustifyContent[JUSTIFY_CONTENT[justifyContent]],
+      direction: Direction[DIRECTION[direction]]
     },
     padding: Dimensions(
       parseDimension(paddingTop),
@@ -170,27 +213,53 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
       parseDimension(marginBottom),
       parseDimension(marginLeft)
     ),
+    position: {
+      kind: PositionType[POSITION_TYPE[position]],
+      top: parseDimension(top),
+      right: parseDimension(right),
+      bottom: parseDimension(bottom),
+      left: parseDimension(left)
+    },
+    display: Display[DISPLAY[display]],
+    visibility: visibility ? Visibility[VISIBILITY[visibility]] : undefined,
+    opacity,
+    zIndex,
+    transform: transform
+      ? {
+          ops: transform.map(parseTransformOp),
+          origin: TransformOrigin(
+            parseDimension(transformOrigin[0]),
+            parseDimension(transformOrigin[1])
+          )
+        }
+      : undefined,
     borderRadius:
       borderTopLeftRadius ||
       borderTopRightRadius ||
       borderBottomLeftRadius ||
       borderBottomRightRadius
-        ? [
-            borderTopLeftRadius,
-            borderTopRightRadius,
-            borderBottomLeftRadius,
-            borderBottomRightRadius
-          ]
+        ? BorderRadius(
+            parseCornerRadius(borderTopLeftRadius),
+            parseCornerRadius(borderTopRightRadius),
+            parseCornerRadius(borderBottomLeftRadius),
+            parseCornerRadius(borderBottomRightRadius)
+          )
         : undefined,
-    boxShadow: shadowColor
-      ? {
-          blur: shadowRadius,
-          spread: shadowSpread,
-          color: parseColor(shadowColor),
-          offset: Vector2f(0, 0)
-        }
+    boxShadows: boxShadow
+      ? splitArgs(boxShadow).map(parseBoxShadow)
+      : shadowColor
+      ? [
+          {
+            blur: shadowRadius,
+            spread: shadowSpread,
+            color: parseColor(shadowColor),
+            offset: Vector2f(0, 0),
+            inset: false
+          }
+        ]
       : undefined,
     backgroundColor: backgroundColor ? parseColor(backgroundColor) : undefined,
+    background: background ? parseBackground(background) : undefined,
     image: backgroundImageUrl ? { url: backgroundImageUrl } : undefined,
     //text,
     border:
@@ -199,23 +268,32 @@ export function compileFlatStyle(style: FlatStyle): SurfaceProps {
             top: {
               width: borderTopWidth,
               color: parseColor(borderTopColor),
-              style: BorderStyle.Solid
+              style: BorderStyle[BORDER_STYLE[borderStyle]]
             },
             right: {
               width: borderRightWidth,
               color: parseColor(borderRightColor),
-              style: BorderStyle.Solid
+              style: BorderStyle[BORDER_STYLE[borderStyle]]
             },
             bottom: {
               width: borderBottomWidth,
               color: parseColor(borderBottomColor),
-              style: BorderStyle.Solid
+              style: BorderStyle[BORDER_STYLE[borderStyle]]
             },
             left: {
               width: borderLeftWidth,
               color: parseColor(borderLeftColor),
-              style: BorderStyle.Solid
-            }
+              style: BorderStyle[BORDER_STYLE[borderStyle]]
+            },
+            image: borderImageSource
+              ? {
+                  source: parseBorderImageSource(borderImageSource),
+                  slice: parseBorderImageSlice(borderImageSlice),
+                  fill: borderImageFill,
+                  repeat:
+                    BorderImageRepeat[BORDER_IMAGE_REPEAT[borderImageRepeat]]
+                }
+              : undefined
           }
         : undefined
   }
@@ -235,12 +313,275 @@ function parseDimension(value?: string | number): Dimension {
   return Dimension.Point(parseFloat(value))
 }
 
+// elliptical radius can be set like in CSS, ie. '10 20' or '50% 25%'
+function parseCornerRadius(value: string | number): CornerRadius {
+  const [x, y = x] = ('' + value).trim().split(/\s+/)
+
+  return CornerRadius(parseDimension(x), parseDimension(y))
+}
+
+// RN style transforms, ie. [{ rotate: '45deg' }, { scale: 2 }]
+function parseTransformOp(op): TransformOp {
+  const [k, v] = Object.entries(op)[0] as [string, any]
+
+  switch (k) {
+    case 'matrix':
+      return TransformOp.Matrix(Matrix(v[0], v[1], v[2], v[3], v[4], v[5]))
+    case 'translateX':
+      return TransformOp.Translate(Vector2f(v, 0))
+    case 'translateY':
+      return TransformOp.Translate(Vector2f(0, v))
+    case 'scale':
+      return TransformOp.Scale(Vector2f(v, v))
+    case 'scaleX':
+      return TransformOp.Scale(Vector2f(v, 1))
+    case 'scaleY':
+      return TransformOp.Scale(Vector2f(1, v))
+    case 'rotate':
+    case 'rotateZ':
+      return TransformOp.Rotate(parseAngle(v))
+    case 'skewX':
+      return TransformOp.Skew(Vector2f(parseAngle(v), 0))
+    case 'skewY':
+      return TransformOp.Skew(Vector2f(0, parseAngle(v)))
+  }
+
+  throw new Error(`transform ${k} is not supported`)
+}
+
+// to degrees
+function parseAngle(value: string | number): number {
+  value = '' + value
+
+  if (value.endsWith('rad')) {
+    return (parseFloat(value) * 180) / Math.PI
+  }
+
+  return parseFloat(value)
+}
+
+// [inset] x y [blur [spread]] color
+function parseBoxShadow(value: string): BoxShadow {
+  const parts = splitArgs(value, /\s/)
+  const inset = parts[0] === 'inset'
+  const [x, y, blur = 0, spread = 0] = parts
+    .slice(inset ? 1 : 0, -1)
+    .map(v => parseFloat(v))
+
+  return {
+    offset: Vector2f(x, y),
+    blur,
+    spread,
+    color: parseColor(parts[parts.length - 1]),
+    inset
+  }
+}
+
+// url or a (linear/radial) gradient
+function parseBorderImageSource(value: string): BorderImageSource {
+  if (!value.includes('gradient(')) {
+    return BorderImageSource.Image({ url: value })
+  }
+
+  const bg = parseBackground(value)
+
+  switch (bg.tag) {
+    case 'LinearGradient':
+      return BorderImageSource.LinearGradient(bg.value)
+    case 'RadialGradient':
+      return BorderImageSource.RadialGradient(bg.value)
+  }
+
+  throw new Error(`border image ${value} is not supported`)
+}
+
+// top, right, bottom, left (like padding in RN)
+function parseBorderImageSlice(
+  value: number | [number, number, number, number]
+): BorderImageSlice {
+  const [top, right, bottom, left] = Array.isArray(value)
+    ? value
+    : [value, value, value, value]
+
+  return BorderImageSlice(top, right, bottom, left)
+}
+
+// CSS gradients, ie. 'linear-gradient(45deg, #f00, #00f 80%)'
+function parseBackground(value: string): Background {
+  const [, fn, args] = value.match(/^\s*([a-z-]+)\((.*)\)\s*$/) || ([] as any)
+  const parts = args === undefined ? [] : splitArgs(args)
+  // direction/shape/position, anything else is the first color stop
+  const first = parts[0] && isGradientConfig(parts[0]) ? parts.shift() : ''
+  const stops = parseColorStops(parts)
+
+  switch (fn) {
+    case 'linear-gradient':
+      return Background.LinearGradient({
+        angle: first ? parseGradientDirection(first) : 180,
+        stops
+      })
+    case 'radial-gradient': {
+      // only farthest-corner ellipse (default) is supported
+      const center = parseGradientCenter(first)
+      const radius = Vector2f(
+        Math.max(center[0], 1 - center[0]) * Math.SQRT2,
+        Math.max(center[1], 1 - center[1]) * Math.SQRT2
+      )
+
+      return Background.RadialGradient({ center, radius, stops })
+    }
+    case 'conic-gradient': {
+      const [, from = 0] = first.match(/from\s+(\S+)/) || []
+
+      return Background.ConicGradient({
+        center: parseGradientCenter(first),
+        angle: parseAngle(from),
+        stops
+      })
+    }
+  }
+
+  throw new Error(`background ${value} is not supported`)
+}
+
+// ie. 'to right', '45deg', 'circle at 25% top', 'from 90deg at center'
+function isGradientConfig(part: string): boolean {
+  return /^(to|at|from|circle|ellipse)\s|^(circle|ellipse)$|\sat\s|^-?[\d.]+(deg|rad)$/.test(part)
+}
+
+// split by top-level commas (or other separator), colors like rgba() are kept together
+function splitArgs(args: string, separator = /,/): string[] {
+  const parts = ['']
+  let depth = 0
+
+  for (const ch of args) {
+    depth += ch === '(' ? 1 : ch === ')' ? -1 : 0
+
+    if (separator.test(ch) && depth === 0) {
+      parts.push('')
+    } else {
+      parts[parts.length - 1] += ch
+    }
+  }
+
+  return parts.map(p => p.trim()).filter(p => p !== '')
+}
+
+// color & optional offset (the last token), ie. 'rgba(0, 0, 0, .5) 50%'
+function splitColorStop(part: string): [string, string | undefined] {
+  const tokens = splitArgs(part, /\s/)
+  const last = tokens[tokens.length - 1]
+
+  return tokens.length > 1 && last.endsWith('%')
+    ? [tokens.slice(0, -1).join(' '), last]
+    : [part, undefined]
+}
+
+// missing offsets are distributed evenly (like in CSS)
+export function parseColorStops(parts: string[]): ColorStop[] {
+  const offsets = parts.map((p, i) => {
+    const [, offset] = splitColorStop(p)
+
+    return offset !== undefined
+      ? parseFloat(offset) / 100
+      : i === 0
+      ? 0
+      : i === parts.length - 1
+      ? 1
+      : undefined
+  })
+
+  for (let i = 1; i < offsets.length; i++) {
+    if (offsets[i] === undefined) {
+      const next = offsets.findIndex((o, j) => j > i && o !== undefined)
+      const step = (offsets[next] - offsets[i - 1]) / (next - i + 1)
+
+      offsets[i] = offsets[i - 1] + step
+    }
+  }
+
+  return parts.map((p, i) => ({
+    offset: offsets[i],
+    color: parseColor(splitColorStop(p)[0])
+  }))
+}
+
+function parseGradientDirection(value: string): number {
+  if (value.startsWith('to ')) {
+    const sides = value.slice(3).split(/\s+/)
+    // corners are only approximated (css angle depends on the aspect ratio)
+    const x = sides.includes('left') ? -1 : sides.includes('right') ? 1 : 0
+    const y = sides.includes('top') ? -1 : sides.includes('bottom') ? 1 : 0
+
+    return (Math.atan2(x, -y) * 180) / Math.PI
+  }
+
+  return parseAngle(value)
+}
+
+// relative, ie. 'circle at 25% top' => [0.25, 0]
+function parseGradientCenter(value: string): Vector2f {
+  const [, x = 'center', y = 'center'] =
+    value.match(/at\s+(\S+)(?:\s+(\S+))?/) || ([] as any)
+
+  return Vector2f(parsePosition(x), parsePosition(y))
+}
+
+function parsePosition(value: string): number {
+  switch (value) {
+    case 'left':
+    case 'top':
+      return 0
+    case 'center':
+      return 0.5
+    case 'right':
+    case 'bottom':
+      return 1
+  }
+
+  return parseFloat(value) / 100
+}
+
+const BORDER_STYLE = {
+  none: 'None',
+  solid: 'Solid',
+  dashed: 'Dashed',
+  dotted: 'Dotted',
+  double: 'Double',
+  groove: 'Groove',
+  ridge: 'Ridge',
+  inset: 'Inset',
+  outset: 'Outset'
+}
+
+const BORDER_IMAGE_REPEAT = {
+  stretch: 'Stretch',
+  repeat: 'Repeat',
+  round: 'Round',
+  space: 'Space'
+}
+
 const OVERFLOW = {
   visible: 'Visible',
   hidden: 'Hidden',
   scroll: 'Scroll'
 }
 
+const POSITION_TYPE = {
+  relative: 'Relative',
+  absolute: 'Absolute'
+}
+
+const DISPLAY = {
+  flex: 'Flex',
+  none: 'None'
+}
+
+const VISIBILITY = {
+  visible: 'Visible',
+  hidden: 'Hidden'
+}
+
 const FLEX_DIRECTION = {
   column: 'Column',
   'column-reverse': 'ColumnReverse',
@@ -266,6 +607,12 @@ const FLEX_ALIGN = {
   'space-around': 'SpaceAround'
 }
 
+const DIRECTION = {
+  inherit: 'Inherit',
+  ltr: 'Ltr',
+  rtl: 'Rtl'
+}
+
 const JUSTIFY_CONTENT = {
   'flex-start': 'FlexStart',
   center: 'Center',
@@ -278,13 +625,23 @@ const JUSTIFY_CONTENT = {
 export interface SurfaceProps {
   overflow?: Overflow
   size?: Size
+  minSize?: Size
+  maxSize?: Size
+  aspectRatio?: number
   flex?: Flex
   flow?: Flow
   padding?: any
   margin?: any
+  position?: Position
+  display?: Display
+  visibility?: Visibility
+  opacity?: number
+  zIndex?: number
+  transform?: Transform
   borderRadius?: BorderRadius
-  boxShadow?: BoxShadow
+  boxShadows?: BoxShadow[]
   backgroundColor?: Color
+  background?: Background
   image?: Image
   text?: Text
   border?: Border
@@ -4,15 +4,14 @@ import View from './View';
 import StyleSheet from '../Stylesheet';
 
 export function ActivityIndicator() {
-  const [alpha, setAlpha] = useState(0)
+  const [angle, setAngle] = useState(0)
 
   useEffect(() => {
-    let a = alpha
     let running = true
 
     requestAnimationFrame(function loop(t) {
       if (running) {
-        setAlpha((t / 100) % 9)
+        setAngle((t * 0.36) % 360)
         requestAnimationFrame(loop)
       }
     })
@@ -20,13 +19,16 @@ export function ActivityIndicator() {
     return () => running = false
   }, [])
 
-  return <View style={[styles.indicator, { backgroundColor: `#ccf${Math.floor(alpha + 1)}` }]}></View>
+  return <View style={[styles.indicator, { transform: [{ rotate: `${angle}deg` }] }]}></View>
 }
 
 const styles = StyleSheet.create({
   indicator: {
     width: 20,
     height: 20,
-    borderRadius: 20
+    borderRadius: 10,
+    borderWidth: 2,
+    borderColor: '#ccf',
+    borderTopColor: '#07f'
   }
 })
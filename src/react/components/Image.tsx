@@ -1,5 +1,6 @@
 import * as React from 'react'
 import View from './View';
+import { Svg } from '../../core/generated';
 import {
   ImageProps,
   ImageSourcePropType,
@@ -11,6 +12,11 @@ export function Image(props: ImageProps) {
     throw new Error('we only support uri sources so far')
   }
 
+  // vector icons are rasterized natively (so they are sharp on HiDPI)
+  if (props.source.uri.endsWith('.svg')) {
+    return <View style={props.style} _svg={Svg.Path(props.source.uri)} />
+  }
+
   return (
     <View style={[props.style, { backgroundImageUrl: props.source.uri }]} />
   )
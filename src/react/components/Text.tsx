@@ -1,22 +1,54 @@
 import * as React from 'react'
 import { TextProps } from '../react-native-types'
 import { parseColor } from '../../core/utils'
-import { TextAlign } from '../../core/generated';
+import { TextAlign, Direction, Ellipsize, WhiteSpace, WordBreak, FontStyle, TextSpan, TextDecoration, TextDecorationStyle, TextShadow, Vector2f, FontFeature } from '../../core/generated';
 import StyleSheet from '../Stylesheet';
 import View from './View'
 
 // we might make it native (host) comp in the future but for now we want to
 // avoid having to mess with `createTextInstance()`, so anything inside <Text>
 // actually gets joined to one string and passed to View via internal `_text`
-export function Text({ children = [], ...rest }: TextProps) {
+//
+// nested <Text> elements become spans (of the same paragraph), the ones with
+// `onPress` are links and they are called if the click was over them
+export function Text({ children = [], numberOfLines = 0, ellipsizeMode = 'tail', ...rest }: TextProps) {
   const {
     _props,
     fontSize = 16,
     color = '#000000',
     lineHeight = 30,
-    textAlign = 'left'
+    textAlign = 'left',
+    writingDirection = 'auto',
+    // RN preserves newlines & spaces
+    whiteSpace = 'pre-wrap',
+    wordBreak = 'normal',
+    textDecorationLine = 'none',
+    textDecorationColor,
+    textDecorationStyle = 'solid',
+    textShadowOffset,
+    textShadowRadius = 0,
+    textShadowColor,
+    letterSpacing = 0,
+    fontVariant = [],
+    fontFeatureSettings = '',
+    fontVariationSettings = ''
   } = StyleSheet.flatten(rest.style || {}) as any
 
+  const content = { text: '', length: 0, spans: [], links: [] }
+  collectText(children, content)
+
+  if (content.links.length) {
+    const { onClick } = rest
+
+    rest.onClick = (e) => {
+      if (e.link !== undefined) {
+        content.links[e.link](e)
+      }
+
+      onClick && onClick(e)
+    }
+  }
+
   // TODO: textContent + (fontFamily, color, ...) in style?
   return (
     <View
@@ -24,14 +56,110 @@ export function Text({ children = [], ...rest }: TextProps) {
         fontSize,
         color: parseColor(color),
         lineHeight,
-        align: TEXT_ALIGN[textAlign],
-        text: [].concat(children).filter(numberOrString).join('')
+        // auto follows the direction (if it's known)
+        align: textAlign === 'auto' && writingDirection === 'rtl' ? TextAlign.Right : TEXT_ALIGN[textAlign],
+        numberOfLines,
+        ellipsize: ELLIPSIZE[ellipsizeMode],
+        whiteSpace: WHITE_SPACE[whiteSpace],
+        wordBreak: WORD_BREAK[wordBreak],
+        spans: content.spans,
+        decoration: parseTextDecoration(textDecorationLine, textDecorationColor, textDecorationStyle),
+        shadows: parseTextShadows(textShadowOffset, textShadowRadius, textShadowColor),
+        letterSpacing,
+        fontFeatures: [
+          ...fontVariant.map(v => FONT_VARIANT_FEATURES[v]).filter(Boolean),
+          ...parseFontSettings(fontFeatureSettings).map(([tag, value]) => ({ tag, value }))
+        ],
+        fontVariations: parseFontSettings(fontVariationSettings).map(([tag, value]) => ({ tag, value })),
+        direction: WRITING_DIRECTION[writingDirection],
+        text: content.text
       }}
       {...rest}
     />
   )
 }
 
+// span offsets are in chars (code points), not in utf-16 units
+function collectText(children, content: { text: string, length: number, spans: TextSpan[], links: any[] }) {
+  for (const child of [].concat(children)) {
+    if (numberOrString(child)) {
+      const str = `${child}`
+
+      content.text += str
+      content.length += Array.from(str).length
+    } else if (child && child.type === Text) {
+      const { children = [], style, onPress } = child.props
+      const span = createSpan(StyleSheet.flatten(style || {}), content.length)
+
+      if (onPress) {
+        span.link = content.links.length
+        content.links.push(onPress)
+      }
+
+      // before the nested ones so they can override it
+      content.spans.push(span)
+      collectText(children, content)
+      span.end = content.length
+    }
+  }
+}
+
+function createSpan({ color, fontSize, fontWeight, fontStyle, textDecorationLine = '' }: any, start): TextSpan {
+  return {
+    start,
+    end: start,
+    color: color ? parseColor(color) : undefined,
+    fontSize,
+    fontWeight: fontWeight === 'bold' ? 700 : fontWeight === 'normal' ? 400 : fontWeight && parseInt(fontWeight),
+    fontStyle: fontStyle === 'italic' ? FontStyle.Italic : fontStyle === 'normal' ? FontStyle.Normal : undefined,
+    underline: textDecorationLine.includes('underline'),
+    link: undefined
+  }
+}
+
+// textDecorationLine can be combined, ie. 'underline line-through'
+function parseTextDecoration(line: string, color, style): TextDecoration | undefined {
+  if (line === 'none') {
+    return undefined
+  }
+
+  return {
+    underline: line.includes('underline'),
+    lineThrough: line.includes('line-through'),
+    overline: line.includes('overline'),
+    color: color ? parseColor(color) : undefined,
+    style: TEXT_DECORATION_STYLE[style]
+  }
+}
+
+// RN supports only one shadow
+function parseTextShadows(offset, radius, color): TextShadow[] {
+  if (!offset && !color) {
+    return []
+  }
+
+  const { width = 0, height = 0 } = offset || {}
+
+  return [{ offset: Vector2f(width, height), blur: radius, color: parseColor(color || '#000000') }]
+}
+
+// '"tnum" 1, "liga"' -> [['tnum', 1], ['liga', 1]]
+function parseFontSettings(settings: string): Array<[string, number]> {
+  return settings
+    .split(',')
+    .map(s => s.trim())
+    .filter(Boolean)
+    .map(s => {
+      const [, tag, value = '1'] = s.match(/^["']?(\w{4})["']?\s*(\S+)?$/) || []
+
+      if (tag === undefined) {
+        throw new Error(`invalid font setting ${s}`)
+      }
+
+      return [tag, value === 'on' ? 1 : value === 'off' ? 0 : parseFloat(value)] as [string, number]
+    })
+}
+
 function numberOrString(v) {
   return typeof v === 'string' || typeof v === 'number';
 }
@@ -39,5 +167,52 @@ function numberOrString(v) {
 const TEXT_ALIGN = {
   left: TextAlign.Left,
   center: TextAlign.Center,
-  right: TextAlign.Right
+  right: TextAlign.Right,
+  justify: TextAlign.Justify,
+  auto: TextAlign.Left
+}
+
+// auto is detected from the text (by the first strong char)
+const WRITING_DIRECTION = {
+  auto: Direction.Inherit,
+  ltr: Direction.Ltr,
+  rtl: Direction.Rtl
+}
+
+const ELLIPSIZE = {
+  head: Ellipsize.Start,
+  middle: Ellipsize.Middle,
+  tail: Ellipsize.End,
+  clip: Ellipsize.None
+}
+
+const WHITE_SPACE = {
+  normal: WhiteSpace.Normal,
+  nowrap: WhiteSpace.NoWrap,
+  pre: WhiteSpace.Pre,
+  'pre-wrap': WhiteSpace.PreWrap,
+  'pre-line': WhiteSpace.PreLine
+}
+
+const WORD_BREAK = {
+  normal: WordBreak.Normal,
+  'break-all': WordBreak.BreakAll,
+  'break-word': WordBreak.BreakWord
+}
+
+const TEXT_DECORATION_STYLE = {
+  solid: TextDecorationStyle.Solid,
+  double: TextDecorationStyle.Double,
+  dotted: TextDecorationStyle.Dotted,
+  dashed: TextDecorationStyle.Dashed,
+  wavy: TextDecorationStyle.Wavy
+}
+
+// RN fontVariant
+const FONT_VARIANT_FEATURES: { [k: string]: FontFeature } = {
+  'small-caps': { tag: 'smcp', value: 1 },
+  'oldstyle-nums': { tag: 'onum', value: 1 },
+  'lining-nums': { tag: 'lnum', value: 1 },
+  'tabular-nums': { tag: 'tnum', value: 1 },
+  'proportional-nums': { tag: 'pnum', value: 1 }
 }
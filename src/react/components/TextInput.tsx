@@ -1,17 +1,34 @@
 import * as React from 'react'
-import { useState, useCallback } from 'react'
+import { useState } from 'react'
 import { TextInputProps } from '../react-native-types'
+import { parseColor } from '../../core/utils'
 import View from './View';
 import { Text } from './Text';
 import StyleSheet from '../Stylesheet';
 
-const TextInput = (props: TextInputProps) => {
+// editing (caret, selection, undo, clipboard, ...) is done natively, the inner
+// text surface only gets `_textInput` and it emits `TextChanged` with the new value
+//
+// the text style is used for the value (and the placeholder) so it's passed
+// through <Text> which is then rendered with the value instead of its content
+const TextInput = ({ value = '', onChangeText, placeholder = '', placeholderTextColor, secureTextEntry = false, multiline = false, style }: TextInputProps) => {
   const [active, setActive] = useState(false)
-  const textInput = useTextValue(props.value, props.onChangeText)
+
+  // not type-checked by <Text> (internal props are not part of TextProps)
+  const native = {
+    _textInput: {
+      value: '' + value,
+      placeholder,
+      placeholderColor: placeholderTextColor && parseColor(placeholderTextColor),
+      secure: secureTextEntry,
+      multiline
+    },
+    onTextChanged: e => onChangeText && onChangeText(e.value)
+  }
 
   return (
-    <View style={[styles.input, active && styles.active, props.style]} {...textInput} onFocus={() => setActive(true)} onBlur={() => setActive(false)}>
-      <Text style={styles.text}>{props.value}</Text>
+    <View style={[styles.input, active && styles.active, style]} onFocus={() => setActive(true)} onBlur={() => setActive(false)}>
+      <Text style={[styles.text, multiline && styles.multiline]} {...native} />
     </View>
   )
 }
@@ -35,31 +52,11 @@ const styles = StyleSheet.create({
   text: {
     lineHeight: 24,
     color: '#556'
+  },
+
+  multiline: {
+    flex: 1
   }
 })
 
 export default TextInput
-
-// this is very basic for now
-export const useTextValue = (value, onChange) => {
-  const onKeyPress = e => {
-      if ( ! e.key) {
-        return
-      }
-
-      onChange(value + e.key)
-  }
-
-  const onKeyDown = e => {
-    if (e.code === 'Backspace') {
-      // TODO: caret position
-      onChange(value.slice(0, -1))
-    }
-  }
-
-  return {
-    value,
-    onKeyDown,
-    onKeyPress
-  }
-}
@@ -22,7 +22,20 @@ export {
 declare module 'react-native' {
   interface ViewStyle {
     backgroundImageUrl?: string
+    // linear-gradient(), radial-gradient() or conic-gradient()
+    background?: string
     shadowSpread?: number
+    // like in CSS, ie. '0 2 4 #0004, inset 0 0 2 #000'
+    boxShadow?: string
+    // url or linear-gradient()/radial-gradient()
+    borderImageSource?: string
+    borderImageSlice?: number | [number, number, number, number]
+    borderImageRepeat?: 'stretch' | 'repeat' | 'round' | 'space'
+    borderImageFill?: boolean
+    transformOrigin?: [string | number, string | number]
+    // keeps the layout but the view is not painted, children inherit it
+    // (unless they have their own visibility, like in CSS)
+    visibility?: 'visible' | 'hidden'
   }
 
   interface ViewProps {
@@ -39,9 +52,19 @@ declare module 'react-native' {
     onMouseOut?: (ev) => void
   }
 
-  type TextValue = string | number | null | undefined | false
+  interface TextStyle {
+    whiteSpace?: 'normal' | 'nowrap' | 'pre' | 'pre-wrap' | 'pre-line'
+    wordBreak?: 'normal' | 'break-all' | 'break-word'
+    // like in CSS, ie. '"tnum" 1, "liga" 0' and '"wght" 650'
+    fontFeatureSettings?: string
+    fontVariationSettings?: string
+  }
+
+  // nested <Text> is a span
+  type TextValue = string | number | null | undefined | false | JSX.Element
 
   interface TextProps {
     children?: TextValue | TextValue[]
+    onClick?: (ev) => void
   }
 }
@@ -140,6 +140,19 @@ function setProp(surface, prop, value) {
     ctx.setText(surface, value ?value :undefined)
   }
 
+  if (prop === '_textInput') {
+    ctx.setTextInput(surface, value ?value :undefined)
+  }
+
+  if (prop === '_svg') {
+    ctx.setSvg(surface, value ?value :undefined)
+  }
+
+  // layout is only sent for surfaces which are interested
+  if (prop === 'onLayout') {
+    ctx.observeLayout(surface, value !== undefined)
+  }
+
   // listeners
   if (prop[0] === 'o' && prop[1] === 'n') {
     ctx['events'].setEventListener(surface, prop, value === 'undefined' ?NOOP :value)
@@ -152,6 +165,18 @@ function patchStyle(surface, props: SurfaceProps, oldProps: SurfaceProps) {
     ctx.setSize(surface, props.size)
   }
 
+  if (props.minSize !== oldProps.minSize) {
+    ctx.setMinSize(surface, props.minSize)
+  }
+
+  if (props.maxSize !== oldProps.maxSize) {
+    ctx.setMaxSize(surface, props.maxSize)
+  }
+
+  if (props.aspectRatio !== oldProps.aspectRatio) {
+    ctx.setAspectRatio(surface, props.aspectRatio)
+  }
+
   if (props.overflow !== oldProps.overflow) {
     ctx.setOverflow(surface, props.overflow)
   }
@@ -172,18 +197,46 @@ function patchStyle(surface, props: SurfaceProps, oldProps: SurfaceProps) {
     ctx.setMargin(surface, props.margin)
   }
 
+  if (props.position !== oldProps.position) {
+    ctx.setPosition(surface, props.position)
+  }
+
+  if (props.display !== oldProps.display) {
+    ctx.setDisplay(surface, props.display)
+  }
+
+  if (props.opacity !== oldProps.opacity) {
+    ctx.setOpacity(surface, props.opacity)
+  }
+
+  if (props.visibility !== oldProps.visibility) {
+    ctx.setVisibility(surface, props.visibility)
+  }
+
+  if (props.zIndex !== oldProps.zIndex) {
+    ctx.setZIndex(surface, props.zIndex)
+  }
+
+  if (props.transform !== oldProps.transform) {
+    ctx.setTransform(surface, props.transform)
+  }
+
   if (props.borderRadius !== oldProps.borderRadius) {
     ctx.setBorderRadius(surface, props.borderRadius)
   }
 
-  if (props.boxShadow !== oldProps.boxShadow) {
-    ctx.setBoxShadow(surface, props.boxShadow)
+  if (props.boxShadows !== oldProps.boxShadows) {
+    ctx.setBoxShadows(surface, props.boxShadows || [])
   }
 
   if (props.backgroundColor !== oldProps.backgroundColor) {
     ctx.setBackgroundColor(surface, props.backgroundColor)
   }
 
+  if (props.background !== oldProps.background) {
+    ctx.setBackground(surface, props.background)
+  }
+
   if (props.image !== oldProps.image) {
     ctx.setImage(surface, props.image)
   }
@@ -217,7 +270,7 @@ declare global {
     }
 
     interface IntrinsicElements {
-      'View': ViewProps & { _text? }
+      'View': ViewProps & { _text?, _textInput?, _svg? }
     }
   }
 }
@@ -287,7 +340,10 @@ export class NotSureWhat {
     onMouseOut: [],
     onMouseDown: [],
     onMouseUp: [],
-    onClick: []
+    onClick: [],
+    onTextChanged: [],
+    onAnimationEnd: [],
+    onLayout: []
   }
   moveTarget = 0
   downTarget = 0
@@ -314,8 +370,9 @@ export class NotSureWhat {
       }
       case 'MouseMove': {
         const prevTarget = this.moveTarget
+        const { link } = event.value
         const target = this.moveTarget = event.value.target
-        this.dispatch(this.listeners.onMouseMove, target, { target })
+        this.dispatch(this.listeners.onMouseMove, target, { target, link })
 
         if (target !== prevTarget) {
           this.dispatch(this.listeners.onMouseOut, prevTarget, { target: prevTarget })
@@ -325,13 +382,14 @@ export class NotSureWhat {
         return
       }
       case 'MouseDown': {
+        const { link } = event.value
         const target = this.downTarget = event.value.target
-        return this.dispatch(this.listeners.onMouseDown, target, { target })
+        return this.dispatch(this.listeners.onMouseDown, target, { target, link })
       }
       case 'MouseUp': {
-        const target = event.value.target
+        const { target, link } = event.value
 
-        this.dispatch(this.listeners.onMouseUp, target, { target })
+        this.dispatch(this.listeners.onMouseUp, target, { target, link })
 
         if (target === this.downTarget) {
           if (target !== this.focusTarget) {
@@ -340,7 +398,7 @@ export class NotSureWhat {
             this.dispatch(this.listeners.onFocus, target, { target })
           }
 
-          this.dispatch(this.listeners.onClick, target, { target })
+          this.dispatch(this.listeners.onClick, target, { target, link })
         }
 
         return
@@ -364,6 +422,28 @@ export class NotSureWhat {
         this.dispatch(this.listeners.onKeyPress, target, { target, key })
         return
       }
+      // native text input was edited (keys it handles are not sent)
+      case 'TextChanged': {
+        const { target, value } = event.value
+
+        this.dispatch(this.listeners.onTextChanged, target, { target, value })
+        return
+      }
+      case 'AnimationEnd': {
+        const { target, property } = event.value
+
+        this.dispatch(this.listeners.onAnimationEnd, target, { target, property })
+        return
+      }
+      case 'LayoutChanged': {
+        const { target, rect: [x, y, width, height] } = event.value
+
+        // not bubbling (like in react-native), the listener might be removed already
+        const listener = this.listeners.onLayout[target] || NOOP
+
+        listener({ target, nativeEvent: { layout: { x, y, width, height } } })
+        return
+      }
     }
   }
 
@@ -407,6 +487,22 @@ interface EventMap {
   onMouseDown: MouseEvent,
   onMouseUp: MouseEvent,
   onClick: MouseEvent
+  onTextChanged: TextChangedEvent
+  onAnimationEnd: AnimationEvent
+  onLayout: LayoutEvent
+}
+
+interface TextChangedEvent {
+  target: number
+  value: string
+}
+
+// relative to the parent
+interface LayoutEvent {
+  target: number
+  nativeEvent: {
+    layout: { x: number, y: number, width: number, height: number }
+  }
 }
 
 type Listener<E> = (ev: E) => any
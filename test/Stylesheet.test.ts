@@ -0,0 +1,67 @@
+import * as assert from 'assert'
+import { compileFlatStyle, parseColorStops } from '../src/react/Stylesheet'
+
+// rounded because of float errors
+const offsets = (parts: string[]) =>
+  parseColorStops(parts).map(s => Math.round(s.offset * 1000) / 1000)
+
+// first/last default to 0/1, missing ones are distributed evenly
+assert.deepStrictEqual(offsets(['#f00', '#00f']), [0, 1])
+assert.deepStrictEqual(offsets(['#f00', '#0f0', '#00f']), [0, 0.5, 1])
+assert.deepStrictEqual(offsets(['#f00', '#0f0 20%', '#00f', '#fff']), [
+  0,
+  0.2,
+  0.6,
+  1
+])
+
+// hard stops
+assert.deepStrictEqual(offsets(['#f00 50%', '#00f 50%']), [0.5, 0.5])
+
+assert.deepStrictEqual(parseColorStops(['#f00', '#00f 100%'])[1], {
+  offset: 1,
+  color: [0, 0, 255, 255]
+})
+
+// colors with spaces & named colors
+assert.deepStrictEqual(parseColorStops(['rgba(0, 0, 0, .5) 50%', 'red']), [
+  { offset: 0.5, color: [0, 0, 0, 128] },
+  { offset: 1, color: [255, 0, 0, 255] }
+])
+
+// any because of the union
+const background = (value: string): any =>
+  compileFlatStyle({ background: value }).background
+
+// the first arg is a direction only if it looks like one
+assert.deepStrictEqual(background('linear-gradient(red, #00f)'), {
+  tag: 'LinearGradient',
+  value: {
+    angle: 180,
+    stops: [
+      { offset: 0, color: [255, 0, 0, 255] },
+      { offset: 1, color: [0, 0, 255, 255] }
+    ]
+  }
+})
+assert.deepStrictEqual(
+  background('linear-gradient(rgba(0, 0, 0, 0) 20%, white)').value.stops,
+  [{ offset: 0.2, color: [0, 0, 0, 0] }, { offset: 1, color: [255, 255, 255, 255] }]
+)
+assert.strictEqual(background('linear-gradient(45deg, red, blue)').value.angle, 45)
+assert.strictEqual(
+  background('linear-gradient(to right, red, blue)').value.angle,
+  90
+)
+assert.deepStrictEqual(
+  background('radial-gradient(circle at 25% top, red, blue)').value.center,
+  [0.25, 0]
+)
+
+const boxShadows = (value: string) =>
+  compileFlatStyle({ boxShadow: value }).boxShadows
+
+assert.deepStrictEqual(boxShadows('0 2 4 rgba(0, 0, 0, .5), inset 1 1 black'), [
+  { offset: [0, 2], blur: 4, spread: 0, color: [0, 0, 0, 128], inset: false },
+  { offset: [1, 1], blur: 0, spread: 0, color: [0, 0, 0, 255], inset: true }
+])